@@ -0,0 +1,289 @@
+//! Process-wide caches behind [`SchemaBridge::schema_ref`] and, with the
+//! `validate` feature, `validate_value`.
+//!
+//! Each type's schema (and compiled validator) is built once and leaked, so
+//! callers get a `&'static` reference they can hold on to. The number of
+//! entries is bounded by the number of types in the program.
+//!
+//! Derived types build their schema through [`build`], which notices when a
+//! type is nested in itself (directly or through other types) and emits a
+//! `Schema::Ref` to it by name instead of recursing forever. Like
+//! [`export_types!`], that name is the type's identifier; the validator
+//! resolves it to the type's cached schema.
+//!
+//! [`SchemaBridge::schema_ref`]: crate::SchemaBridge::schema_ref
+//! [`export_types!`]: crate::export_types
+
+#[cfg(feature = "validate")]
+use crate::CompiledSchema;
+use crate::{Schema, SchemaBridge};
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+type Cache<V> = RwLock<HashMap<(TypeId, bool), &'static V>>;
+
+/// The schema of `T` (its input side when `input`), built by `build` on
+/// first use.
+pub(crate) fn cached<T: ?Sized + 'static>(
+    input: bool,
+    build: impl FnOnce() -> &'static Schema,
+) -> &'static Schema {
+    static CACHE: OnceLock<Cache<Schema>> = OnceLock::new();
+    get_or_insert::<T, _>(&CACHE, input, build)
+}
+
+/// The compiled schema of `T`, built by `build` on first use.
+#[cfg(feature = "validate")]
+pub(crate) fn compiled<T: ?Sized + 'static>(
+    input: bool,
+    build: impl FnOnce() -> CompiledSchema,
+) -> &'static CompiledSchema {
+    static CACHE: OnceLock<Cache<CompiledSchema>> = OnceLock::new();
+    get_or_insert::<T, _>(&CACHE, input, || Box::leak(Box::new(build())))
+}
+
+fn get_or_insert<T: ?Sized + 'static, V: 'static>(
+    cache: &'static OnceLock<Cache<V>>,
+    input: bool,
+    build: impl FnOnce() -> &'static V,
+) -> &'static V {
+    let cache = cache.get_or_init(Default::default);
+    let key = (TypeId::of::<T>(), input);
+    if let Some(value) = cache
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+    {
+        return value;
+    }
+
+    // Built outside the lock: nested types look themselves up while building.
+    // A thread losing the race leaks its copy, once per type at most.
+    let value = build();
+    let mut cache = cache.write().unwrap_or_else(PoisonError::into_inner);
+    cache.entry(key).or_insert(value)
+}
+
+pub(crate) fn leak(schema: Schema) -> &'static Schema {
+    Box::leak(Box::new(schema))
+}
+
+/// A schema being built on this thread.
+struct Building {
+    ty: TypeId,
+    input: bool,
+    /// Name of the `Ref` standing in for the schema inside itself
+    name: String,
+    recursed: bool,
+    /// Refers to a schema further down the stack, so it only holds in this
+    /// context and must not be cached
+    tainted: bool,
+}
+
+/// How the outermost [`build`] that just finished went.
+struct Outcome {
+    recursed: bool,
+    tainted: bool,
+}
+
+thread_local! {
+    static BUILDING: RefCell<Vec<Building>> = const { RefCell::new(Vec::new()) };
+    static LAST: RefCell<Option<Outcome>> = const { RefCell::new(None) };
+    /// Set by [`input`] for the type whose input side it is building: whether
+    /// that side differs from the output, and so is named `{name}Input`
+    static INPUT_RENAMED: Cell<Option<(TypeId, bool)>> = const { Cell::new(None) };
+}
+
+/// The cached schema behind each `Ref` emitted by [`build`], for the
+/// validator to resolve. `None` when types from different modules share a
+/// name.
+type References = RwLock<HashMap<String, Option<(TypeId, fn() -> &'static Schema)>>>;
+static REFERENCES: OnceLock<References> = OnceLock::new();
+
+/// Schema of `T` (its input side when `input`) built by `body`, or a
+/// `Schema::Ref` named after `T` when that schema is already being built
+/// further up this thread's stack. Used by the derive.
+#[doc(hidden)]
+pub fn build<T: SchemaBridge + 'static>(
+    input: bool,
+    name: &str,
+    body: impl FnOnce() -> Schema,
+) -> Schema {
+    if let Some(reference) = reference::<T>(input) {
+        return reference;
+    }
+    let renamed = input
+        && match INPUT_RENAMED.take() {
+            Some((ty, renamed)) if ty == TypeId::of::<T>() => renamed,
+            _ => !std::ptr::eq(T::schema_input_ref(), T::schema_ref()),
+        };
+    BUILDING.with_borrow_mut(|stack| {
+        stack.push(Building {
+            ty: TypeId::of::<T>(),
+            input,
+            name: if renamed {
+                format!("{name}Input")
+            } else {
+                name.to_string()
+            },
+            recursed: false,
+            tainted: false,
+        })
+    });
+
+    // Popped on unwind too, so a panicking nested impl leaves no stale entry
+    struct Pop;
+    impl Drop for Pop {
+        fn drop(&mut self) {
+            let building = BUILDING
+                .with_borrow_mut(Vec::pop)
+                .expect("schema build stack");
+            LAST.set(Some(Outcome {
+                recursed: building.recursed,
+                tainted: building.tainted,
+            }));
+        }
+    }
+    let _pop = Pop;
+    body()
+}
+
+/// `Schema::Shared` reference to the cached schema of `T` (its input side
+/// when `input`), or a `Schema::Ref` while that schema is being built on
+/// this thread. Used by the derive.
+#[doc(hidden)]
+pub fn shared<T: SchemaBridge + 'static>(input: bool) -> Schema {
+    reference::<T>(input).unwrap_or_else(|| {
+        Schema::Shared(if input {
+            T::schema_input_ref()
+        } else {
+            T::schema_ref()
+        })
+    })
+}
+
+/// The output schema of `T`, kept in `cell` unless it only holds inside a
+/// schema still being built. Used by the derive.
+#[doc(hidden)]
+pub fn output<T: SchemaBridge + 'static>(
+    cell: &'static OnceLock<&'static Schema>,
+) -> &'static Schema {
+    if let Some(schema) = cell.get() {
+        return schema;
+    }
+    LAST.take();
+    let schema = T::to_schema();
+    keep(cell, schema, LAST.take())
+}
+
+/// The input schema of `T`: its output schema when both sides match, kept
+/// in `cell` like [`output`]. Used by the derive.
+#[doc(hidden)]
+pub fn input<T: SchemaBridge + 'static>(
+    cell: &'static OnceLock<&'static Schema>,
+) -> &'static Schema {
+    if let Some(schema) = cell.get() {
+        return schema;
+    }
+    let output = T::schema_ref();
+    let build = |renamed| {
+        LAST.take();
+        INPUT_RENAMED.set(Some((TypeId::of::<T>(), renamed)));
+        let schema = T::to_schema_input();
+        INPUT_RENAMED.take();
+        (schema, LAST.take())
+    };
+
+    // Built with references named like the output first, since the two
+    // sides can only be compared that way
+    let (schema, outcome) = build(false);
+    if schema == *output {
+        return match outcome {
+            Some(Outcome { tainted: false, .. }) => cell.get_or_init(|| output),
+            _ => output,
+        };
+    }
+    let (schema, outcome) = match outcome {
+        Some(Outcome { recursed: true, .. }) => build(true),
+        outcome => (schema, outcome),
+    };
+    keep(cell, schema, outcome)
+}
+
+fn keep(
+    cell: &'static OnceLock<&'static Schema>,
+    schema: Schema,
+    outcome: Option<Outcome>,
+) -> &'static Schema {
+    let schema = leak(schema);
+    match outcome {
+        Some(Outcome { tainted: false, .. }) => cell.get_or_init(|| schema),
+        _ => schema,
+    }
+}
+
+/// A `Ref` to the schema of `T` if it is being built on this thread.
+fn reference<T: SchemaBridge + 'static>(input: bool) -> Option<Schema> {
+    let ty = TypeId::of::<T>();
+    let name = BUILDING.with_borrow_mut(|stack| {
+        let index = stack
+            .iter()
+            .rposition(|building| building.ty == ty && building.input == input)?;
+        stack[index].recursed = true;
+        for building in &mut stack[index + 1..] {
+            building.tainted = true;
+        }
+        Some(stack[index].name.clone())
+    })?;
+
+    let target: fn() -> &'static Schema = if input {
+        T::schema_input_ref
+    } else {
+        T::schema_ref
+    };
+    REFERENCES
+        .get_or_init(Default::default)
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(name.clone())
+        .and_modify(|entry| {
+            if entry.is_some_and(|(other, _)| other != ty) {
+                *entry = None;
+            }
+        })
+        .or_insert(Some((ty, target)));
+    Some(Schema::Ref(name))
+}
+
+/// The cached schema a `Ref` emitted by [`build`] stands for.
+#[cfg(feature = "validate")]
+pub(crate) fn recursive(name: &str) -> Option<&'static Schema> {
+    let (_, target) = REFERENCES
+        .get()?
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name)
+        .copied()
+        .flatten()?;
+    Some(target())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_once_per_type_and_side() {
+        struct Probe;
+
+        let first = cached::<Probe>(false, || leak(Schema::Integer));
+        let again = cached::<Probe>(false, || unreachable!());
+        assert!(std::ptr::eq(first, again));
+
+        let input = cached::<Probe>(true, || leak(Schema::Number));
+        assert_eq!(*input, Schema::Number);
+        assert!(!std::ptr::eq(first, input));
+    }
+}
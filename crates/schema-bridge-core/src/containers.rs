@@ -0,0 +1,197 @@
+//! `SchemaBridge` impls for third-party collections and wrappers, each
+//! behind a cargo feature named after the crate. They describe their
+//! contents like the std types they stand in for.
+
+#![cfg(any(
+    feature = "indexmap",
+    feature = "smallvec",
+    feature = "arrayvec",
+    feature = "bytes",
+    feature = "ordered-float"
+))]
+
+// Serialized in insertion order, so the record is wrapped in `Ordered`
+#[cfg(feature = "indexmap")]
+mod indexmap_impls {
+    use crate::{Schema, SchemaBridge, SchemaKey};
+
+    impl<K: SchemaKey, V: SchemaBridge, S> SchemaBridge for indexmap::IndexMap<K, V, S> {
+        fn to_schema() -> Schema {
+            Schema::Ordered(Box::new(crate::record::<K>(V::shared_schema())))
+        }
+
+        fn to_schema_input() -> Schema {
+            Schema::Ordered(Box::new(crate::record::<K>(V::shared_schema_input())))
+        }
+    }
+
+    impl<T: SchemaBridge, S> SchemaBridge for indexmap::IndexSet<T, S> {
+        fn to_schema() -> Schema {
+            Schema::Array(Box::new(T::shared_schema()))
+        }
+
+        fn to_schema_input() -> Schema {
+            Schema::Array(Box::new(T::shared_schema_input()))
+        }
+    }
+}
+
+#[cfg(feature = "smallvec")]
+mod smallvec_impls {
+    use crate::{Schema, SchemaBridge};
+
+    impl<A> SchemaBridge for smallvec::SmallVec<A>
+    where
+        A: smallvec::Array,
+        A::Item: SchemaBridge,
+    {
+        fn to_schema() -> Schema {
+            Schema::Array(Box::new(A::Item::shared_schema()))
+        }
+
+        fn to_schema_input() -> Schema {
+            Schema::Array(Box::new(A::Item::shared_schema_input()))
+        }
+    }
+}
+
+// Fixed capacity: deserializing more than `CAP` elements fails
+#[cfg(feature = "arrayvec")]
+mod arrayvec_impls {
+    use crate::{Constraints, Schema, SchemaBridge, SchemaKey};
+
+    fn bounded(schema: Schema, cap: usize) -> Schema {
+        schema.constrained(Constraints {
+            max_len: Some(cap),
+            ..Default::default()
+        })
+    }
+
+    impl<T: SchemaBridge, const CAP: usize> SchemaBridge for arrayvec::ArrayVec<T, CAP> {
+        fn to_schema() -> Schema {
+            bounded(Schema::Array(Box::new(T::shared_schema())), CAP)
+        }
+
+        fn to_schema_input() -> Schema {
+            bounded(Schema::Array(Box::new(T::shared_schema_input())), CAP)
+        }
+    }
+
+    // `CAP` counts UTF-8 bytes, so it is an upper bound on the length in characters
+    impl<const CAP: usize> SchemaBridge for arrayvec::ArrayString<CAP> {
+        fn to_schema() -> Schema {
+            bounded(Schema::String, CAP)
+        }
+    }
+
+    impl<const CAP: usize> SchemaKey for arrayvec::ArrayString<CAP> {}
+}
+
+// Written with `serialize_bytes`, like `serde_bytes`
+#[cfg(feature = "bytes")]
+mod bytes_impls {
+    use crate::{BytesEncoding, Schema, SchemaBridge};
+
+    impl SchemaBridge for bytes::Bytes {
+        fn to_schema() -> Schema {
+            Schema::Bytes(BytesEncoding::Array)
+        }
+    }
+
+    impl SchemaBridge for bytes::BytesMut {
+        fn to_schema() -> Schema {
+            Schema::Bytes(BytesEncoding::Array)
+        }
+    }
+}
+
+// Transparent wrappers, often used to make floats usable as map keys
+#[cfg(feature = "ordered-float")]
+mod ordered_float_impls {
+    use crate::{Schema, SchemaBridge, SchemaKey};
+
+    impl<T: SchemaBridge> SchemaBridge for ordered_float::OrderedFloat<T> {
+        fn to_schema() -> Schema {
+            T::to_schema()
+        }
+    }
+
+    impl<T: SchemaBridge> SchemaBridge for ordered_float::NotNan<T> {
+        fn to_schema() -> Schema {
+            T::to_schema()
+        }
+    }
+
+    impl<T: SchemaKey> SchemaKey for ordered_float::OrderedFloat<T> {
+        fn to_key_schema() -> Schema {
+            T::to_key_schema()
+        }
+    }
+
+    impl<T: SchemaKey> SchemaKey for ordered_float::NotNan<T> {
+        fn to_key_schema() -> Schema {
+            T::to_key_schema()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SchemaBridge;
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn indexmap_is_an_ordered_record() {
+        use crate::Schema;
+
+        let schema = indexmap::IndexMap::<String, u8>::to_schema();
+        assert!(
+            matches!(&schema, Schema::Ordered(record) if matches!(**record, Schema::Record { .. }))
+        );
+        assert_eq!(
+            indexmap::IndexMap::<u32, bool>::to_ts(),
+            "Record<string, boolean>"
+        );
+        assert_eq!(indexmap::IndexSet::<String>::to_ts(), "string[]");
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn smallvec_is_an_array() {
+        assert_eq!(smallvec::SmallVec::<[u16; 4]>::to_ts(), "number[]");
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn arrayvec_capacity_is_max_len() {
+        let schema = arrayvec::ArrayVec::<u8, 8>::to_schema();
+        assert_eq!(schema.constraints().max_len, Some(8));
+        assert_eq!(arrayvec::ArrayString::<16>::to_ts(), "string");
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_are_native_bytes() {
+        use crate::{BytesEncoding, Schema};
+
+        assert_eq!(
+            bytes::Bytes::to_schema(),
+            Schema::Bytes(BytesEncoding::Array)
+        );
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn ordered_floats_are_numbers() {
+        use crate::{Schema, SchemaKey};
+
+        assert_eq!(
+            ordered_float::OrderedFloat::<f64>::to_schema(),
+            Schema::Number
+        );
+        assert_eq!(
+            ordered_float::NotNan::<f32>::to_key_schema(),
+            f32::to_key_schema()
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! `SchemaBridge` impls for third-party types, each behind a cargo feature
+//! named after the crate.
+//!
+//! Types that serialize as strings map to `Schema::String` carrying a
+//! [`Constraints::format`](crate::Constraints::format). Those also work as
+//! map keys.
+
+#![cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "uuid",
+    feature = "url",
+    feature = "rust_decimal"
+))]
+
+use crate::{Constraints, Schema, SchemaBridge, SchemaKey};
+
+/// A string in the given well-known `format`.
+fn formatted(format: &str) -> Schema {
+    Schema::String.constrained(Constraints {
+        format: Some(format.to_string()),
+        ..Default::default()
+    })
+}
+
+// RFC 3339 / ISO 8601 strings, as chrono's serde impls write them
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+
+    impl<Tz: chrono::TimeZone> SchemaBridge for chrono::DateTime<Tz> {
+        fn to_schema() -> Schema {
+            formatted("date-time")
+        }
+    }
+
+    impl SchemaBridge for chrono::NaiveDateTime {
+        fn to_schema() -> Schema {
+            formatted("date-time-local")
+        }
+    }
+
+    impl SchemaBridge for chrono::NaiveDate {
+        fn to_schema() -> Schema {
+            formatted("date")
+        }
+    }
+
+    impl SchemaBridge for chrono::NaiveTime {
+        fn to_schema() -> Schema {
+            formatted("time")
+        }
+    }
+
+    impl<Tz: chrono::TimeZone> SchemaKey for chrono::DateTime<Tz> {}
+    impl SchemaKey for chrono::NaiveDateTime {}
+    impl SchemaKey for chrono::NaiveDate {}
+    impl SchemaKey for chrono::NaiveTime {}
+}
+
+// time writes strings only with its `serde-human-readable` feature, which is
+// up to the user; otherwise tuples of the components. Its date-times are not
+// RFC 3339 ("2020-01-01 00:00:00.0 +00:00:00"), so they get a pattern instead
+// of a format
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+    use std::sync::OnceLock;
+
+    const DATE_TIME: &str = r"^-?\d{4,6}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}(\.\d+)?";
+
+    /// Whether time's types serialize as strings in this build.
+    fn human_readable() -> bool {
+        static HUMAN_READABLE: OnceLock<bool> = OnceLock::new();
+        *HUMAN_READABLE.get_or_init(|| {
+            serde_json::to_value(time::Date::MIN).is_ok_and(|value| value.is_string())
+        })
+    }
+
+    fn patterned(pattern: String) -> Schema {
+        Schema::String.constrained(Constraints {
+            pattern: Some(pattern),
+            ..Default::default()
+        })
+    }
+
+    fn ranged(min: f64, max: f64) -> Schema {
+        Schema::Integer.constrained(Constraints::default().min(min).max(max))
+    }
+
+    /// `(year, ordinal)`
+    fn date_parts() -> Vec<Schema> {
+        vec![Schema::Integer, ranged(1.0, 366.0)]
+    }
+
+    /// `(hour, minute, second, nanosecond)`
+    fn time_parts() -> Vec<Schema> {
+        vec![
+            ranged(0.0, 23.0),
+            ranged(0.0, 59.0),
+            ranged(0.0, 59.0),
+            ranged(0.0, 999_999_999.0),
+        ]
+    }
+
+    /// `(hours, minutes, seconds)` of the UTC offset
+    fn offset_parts() -> Vec<Schema> {
+        vec![
+            ranged(-25.0, 25.0),
+            ranged(-59.0, 59.0),
+            ranged(-59.0, 59.0),
+        ]
+    }
+
+    pub(super) fn offset_date_time(human_readable: bool) -> Schema {
+        if human_readable {
+            patterned(format!(r"{DATE_TIME} [+-]\d{{2}}:\d{{2}}:\d{{2}}$"))
+        } else {
+            Schema::Tuple([date_parts(), time_parts(), offset_parts()].concat())
+        }
+    }
+
+    pub(super) fn primitive_date_time(human_readable: bool) -> Schema {
+        if human_readable {
+            patterned(format!("{DATE_TIME}$"))
+        } else {
+            Schema::Tuple([date_parts(), time_parts()].concat())
+        }
+    }
+
+    pub(super) fn date(human_readable: bool) -> Schema {
+        if human_readable {
+            formatted("date")
+        } else {
+            Schema::Tuple(date_parts())
+        }
+    }
+
+    pub(super) fn time(human_readable: bool) -> Schema {
+        if human_readable {
+            formatted("time")
+        } else {
+            Schema::Tuple(time_parts())
+        }
+    }
+
+    impl SchemaBridge for time::OffsetDateTime {
+        fn to_schema() -> Schema {
+            offset_date_time(human_readable())
+        }
+    }
+
+    impl SchemaBridge for time::PrimitiveDateTime {
+        fn to_schema() -> Schema {
+            primitive_date_time(human_readable())
+        }
+    }
+
+    impl SchemaBridge for time::Date {
+        fn to_schema() -> Schema {
+            date(human_readable())
+        }
+    }
+
+    impl SchemaBridge for time::Time {
+        fn to_schema() -> Schema {
+            time(human_readable())
+        }
+    }
+
+    // Only the strings can be map keys
+    impl SchemaKey for time::OffsetDateTime {}
+    impl SchemaKey for time::PrimitiveDateTime {}
+    impl SchemaKey for time::Date {}
+    impl SchemaKey for time::Time {}
+}
+
+#[cfg(feature = "uuid")]
+impl SchemaBridge for uuid::Uuid {
+    fn to_schema() -> Schema {
+        formatted("uuid")
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl SchemaKey for uuid::Uuid {}
+
+#[cfg(feature = "url")]
+impl SchemaBridge for url::Url {
+    fn to_schema() -> Schema {
+        formatted("uri")
+    }
+}
+
+#[cfg(feature = "url")]
+impl SchemaKey for url::Url {}
+
+// Serialized as a string unless rust_decimal's `serde-float` is enabled
+#[cfg(feature = "rust_decimal")]
+impl SchemaBridge for rust_decimal::Decimal {
+    fn to_schema() -> Schema {
+        formatted("decimal")
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl SchemaKey for rust_decimal::Decimal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format_of<T: SchemaBridge>() -> Option<String> {
+        assert_eq!(T::to_ts(), "string");
+        T::to_schema().constraints().format
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_types() {
+        assert_eq!(
+            format_of::<chrono::DateTime<chrono::Utc>>().as_deref(),
+            Some("date-time")
+        );
+        assert_eq!(
+            format_of::<chrono::NaiveDateTime>().as_deref(),
+            Some("date-time-local")
+        );
+        assert_eq!(format_of::<chrono::NaiveDate>().as_deref(), Some("date"));
+        assert_eq!(format_of::<chrono::NaiveTime>().as_deref(), Some("time"));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_types() {
+        use time_impls::{date, offset_date_time, primitive_date_time, time};
+
+        // Whichever encoding this build's time uses
+        if serde_json::to_value(time::Date::MIN).unwrap().is_string() {
+            assert_eq!(format_of::<time::Date>().as_deref(), Some("date"));
+        } else {
+            assert_eq!(time::Date::to_schema(), date(false));
+        }
+
+        assert_eq!(date(true).constraints().format.as_deref(), Some("date"));
+        assert_eq!(time(true).constraints().format.as_deref(), Some("time"));
+        assert_eq!(offset_date_time(true).constraints().format, None);
+        assert_eq!(
+            offset_date_time(true).constraints().pattern.as_deref(),
+            Some(r"^-?\d{4,6}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}(\.\d+)? [+-]\d{2}:\d{2}:\d{2}$")
+        );
+
+        let render = |schema: Schema| crate::render_ts(&schema, &Default::default());
+        assert_eq!(render(date(false)), "[number, number]");
+        assert_eq!(render(time(false)), "[number, number, number, number]");
+        let Schema::Tuple(parts) = primitive_date_time(false) else {
+            panic!("Expected Schema::Tuple");
+        };
+        assert_eq!(parts.len(), 6);
+        let Schema::Tuple(parts) = offset_date_time(false) else {
+            panic!("Expected Schema::Tuple");
+        };
+        assert_eq!(parts[1].constraints().max, Some(366.0));
+        assert_eq!(parts[6].constraints().min, Some(-25.0));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_type() {
+        assert_eq!(format_of::<uuid::Uuid>().as_deref(), Some("uuid"));
+        assert_eq!(
+            std::collections::HashMap::<uuid::Uuid, u8>::to_ts(),
+            "Record<string, number>"
+        );
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_type() {
+        assert_eq!(format_of::<url::Url>().as_deref(), Some("uri"));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn decimal_type() {
+        assert_eq!(
+            format_of::<rust_decimal::Decimal>().as_deref(),
+            Some("decimal")
+        );
+    }
+}
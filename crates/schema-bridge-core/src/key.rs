@@ -0,0 +1,165 @@
+//! Map keys. JSON object keys are always strings, so a map's key type is
+//! described by what serde writes in key position rather than by its own
+//! `to_schema()`: integers become numeric strings, and enums a closed set
+//! of names that need not all be present.
+
+use crate::{Constraints, Schema, SchemaBridge};
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A type that can be the key of a `HashMap` or `BTreeMap` in a schema.
+///
+/// Derived for unit-only enums and for newtypes over a key type; structs,
+/// sequences and other types serde cannot write as a string have no impl.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a map key in a schema",
+    label = "not a string-like key",
+    note = "JSON object keys are strings: use a string, integer, char or bool, a unit-only enum, or a newtype over one of them"
+)]
+pub trait SchemaKey: SchemaBridge {
+    /// Schema of the key as it appears in a JSON object. Defaults to
+    /// [`SchemaBridge::to_schema`], which is right for types that already
+    /// serialize as strings.
+    fn to_key_schema() -> Schema {
+        Self::to_schema()
+    }
+}
+
+/// Key schema of a newtype over `Inner`: the newtype's own schema (keeping
+/// its brand and constraints) when `Inner` is a string key, otherwise the
+/// stringified form of `Inner`. Used by the derive.
+#[doc(hidden)]
+pub fn newtype_key_schema<Newtype: SchemaBridge, Inner: SchemaKey>() -> Schema {
+    let key = Inner::to_key_schema();
+    if key == Inner::to_schema() {
+        Newtype::to_schema()
+    } else {
+        key
+    }
+}
+
+/// Key schema of a `#[repr]` enum written by `serde_repr`: its discriminants
+/// as strings. Used by the derive.
+#[doc(hidden)]
+pub fn numeric_enum_key_schema(values: &[i64]) -> Schema {
+    Schema::Enum(values.iter().map(i64::to_string).collect())
+}
+
+fn numeric(pattern: &str) -> Schema {
+    Schema::String.constrained(Constraints {
+        pattern: Some(pattern.to_string()),
+        ..Default::default()
+    })
+}
+
+const UNSIGNED: &str = "^[0-9]+$";
+const SIGNED: &str = "^-?[0-9]+$";
+const POSITIVE: &str = "^[1-9][0-9]*$";
+const NON_ZERO: &str = "^-?[1-9][0-9]*$";
+const FLOAT: &str = "^-?[0-9]+(\\.[0-9]+)?([eE][+-]?[0-9]+)?$";
+
+macro_rules! impl_string_key {
+    ($($ty:ty),+ $(,)?) => {
+        $(impl SchemaKey for $ty {})+
+    };
+}
+
+impl_string_key!(
+    String,
+    str,
+    char,
+    Path,
+    PathBuf,
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6,
+);
+
+macro_rules! impl_numeric_key {
+    ($pattern:expr => $($ty:ty),+) => {
+        $(impl SchemaKey for $ty {
+            fn to_key_schema() -> Schema {
+                numeric($pattern)
+            }
+        })+
+    };
+}
+
+impl_numeric_key!(UNSIGNED => u8, u16, u32, u64, u128, usize);
+impl_numeric_key!(SIGNED => i8, i16, i32, i64, i128, isize);
+impl_numeric_key!(POSITIVE => NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+impl_numeric_key!(NON_ZERO => NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+impl_numeric_key!(FLOAT => f32, f64);
+
+impl SchemaKey for bool {
+    fn to_key_schema() -> Schema {
+        Schema::Enum(vec!["true".to_string(), "false".to_string()])
+    }
+}
+
+macro_rules! impl_forwarding_key {
+    ($($ty:ty),+ $(,)?) => {
+        $(impl<T: SchemaKey + ?Sized> SchemaKey for $ty {
+            fn to_key_schema() -> Schema {
+                T::to_key_schema()
+            }
+        })+
+    };
+}
+
+impl_forwarding_key!(&T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: SchemaKey + ToOwned + ?Sized> SchemaKey for Cow<'_, T> {
+    fn to_key_schema() -> Schema {
+        T::to_key_schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn string_keys_keep_their_schema() {
+        assert_eq!(String::to_key_schema(), Schema::String);
+        assert_eq!(<&str>::to_key_schema(), Schema::String);
+        assert_eq!(HashMap::<String, u8>::to_ts(), "Record<string, number>");
+    }
+
+    #[test]
+    fn integer_keys_are_numeric_strings() {
+        let schema = HashMap::<u32, bool>::to_schema();
+        let Schema::Record { key, .. } = &schema else {
+            panic!("Expected Schema::Record");
+        };
+        assert_eq!(key.constraints().pattern.as_deref(), Some(UNSIGNED));
+        assert_eq!(BTreeMap::<i64, bool>::to_ts(), "Record<string, boolean>");
+    }
+
+    #[test]
+    fn bool_keys_are_a_partial_record() {
+        assert_eq!(
+            HashMap::<bool, u8>::to_ts(),
+            "Partial<Record<'true' | 'false', number>>"
+        );
+    }
+
+    #[test]
+    fn numeric_enum_keys() {
+        assert_eq!(
+            numeric_enum_key_schema(&[1, -2]),
+            Schema::Enum(vec!["1".to_string(), "-2".to_string()])
+        );
+    }
+}
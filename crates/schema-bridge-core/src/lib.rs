@@ -1,10 +1,41 @@
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::sync::Arc;
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+#[doc(hidden)]
+pub mod cache;
+mod containers;
+mod ecosystem;
+pub mod key;
+mod ts;
+#[cfg(feature = "validate")]
+mod validate;
+
+pub use key::SchemaKey;
+
+pub use ts::{
+    export_to_file_with, generate_ts_file_with, render_declaration, render_ts, AnyStyle,
+    Declaration, DeclarationKind, DeclarationStyle, OptionalStyle, QuoteStyle, TsConfig,
+};
+#[cfg(feature = "validate")]
+pub use validate::{
+    from_str_validated, from_value_validated, validate_value, CompileError, CompiledSchema,
+    DeserializeError, ValidationError,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Schema {
     String,
     Number,
@@ -15,6 +46,8 @@ pub enum Schema {
     Array(Box<Schema>),
     Object(Vec<Field>),
     Enum(Vec<String>),
+    /// Integer-valued enum, e.g. a `#[schema_bridge(numeric)]` enum serialized via `serde_repr`
+    NumericEnum(Vec<i64>),
     Union(Vec<Schema>),
     Tuple(Vec<Schema>),
     Ref(String),
@@ -22,17 +55,129 @@ pub enum Schema {
         key: Box<Schema>,
         value: Box<Schema>,
     },
+    /// A `Record` whose entries keep their insertion order on the wire, e.g.
+    /// an `IndexMap`; validates and renders like the record itself
+    Ordered(Box<Schema>),
+    /// Nominal newtype: validates like `schema` but keeps its own identity,
+    /// rendered as `T & { readonly __brand: 'Name' }`
+    Branded {
+        name: String,
+        schema: Box<Schema>,
+    },
+    /// `schema` with constraints that travel with the type itself, e.g. a
+    /// `#[schema(min_len = 1)] struct Name(String)` newtype
+    Constrained {
+        schema: Box<Schema>,
+        constraints: Constraints,
+    },
+    /// Hand-written TypeScript type, e.g. from `#[schema(ts_type = "...")]`;
+    /// `schema` describes the value for everything else
+    Custom {
+        ts: String,
+        schema: Box<Schema>,
+    },
+    /// Binary data, e.g. a `#[serde(with = "serde_bytes")]` or base64 field
+    Bytes(BytesEncoding),
+    /// Another type's cached schema, referenced rather than copied wherever
+    /// that type is nested (see [`SchemaBridge::shared_schema`]). Serializes
+    /// and compares as the schema it points to.
+    #[serde(untagged, skip_deserializing)]
+    Shared(&'static Schema),
 }
 
+impl PartialEq for Schema {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.resolved(), other.resolved()) {
+            (Schema::String, Schema::String)
+            | (Schema::Number, Schema::Number)
+            | (Schema::Integer, Schema::Integer)
+            | (Schema::Boolean, Schema::Boolean)
+            | (Schema::Null, Schema::Null)
+            | (Schema::Any, Schema::Any) => true,
+            (Schema::Array(a), Schema::Array(b)) | (Schema::Ordered(a), Schema::Ordered(b)) => {
+                a == b
+            }
+            (Schema::Object(a), Schema::Object(b)) => a == b,
+            (Schema::Enum(a), Schema::Enum(b)) => a == b,
+            (Schema::NumericEnum(a), Schema::NumericEnum(b)) => a == b,
+            (Schema::Union(a), Schema::Union(b)) | (Schema::Tuple(a), Schema::Tuple(b)) => a == b,
+            (Schema::Ref(a), Schema::Ref(b)) => a == b,
+            (
+                Schema::Record { key, value },
+                Schema::Record {
+                    key: other_key,
+                    value: other_value,
+                },
+            ) => key == other_key && value == other_value,
+            (
+                Schema::Branded { name, schema },
+                Schema::Branded {
+                    name: other_name,
+                    schema: other_schema,
+                },
+            ) => name == other_name && schema == other_schema,
+            (
+                Schema::Constrained {
+                    schema,
+                    constraints,
+                },
+                Schema::Constrained {
+                    schema: other_schema,
+                    constraints: other_constraints,
+                },
+            ) => schema == other_schema && constraints == other_constraints,
+            (
+                Schema::Custom { ts, schema },
+                Schema::Custom {
+                    ts: other_ts,
+                    schema: other_schema,
+                },
+            ) => ts == other_ts && schema == other_schema,
+            (Schema::Bytes(a), Schema::Bytes(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// How a byte buffer is written on the wire.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BytesEncoding {
+    /// Base64 text, rendered as `string`
+    Base64,
+    /// Hexadecimal text, rendered as `string`
+    Hex,
+    /// Native bytes (`serde_bytes`), rendered as `Uint8Array`
+    Array,
+}
+
+/// A property of a [`Schema::Object`].
+///
+/// Build one with [`Field::new`] or [`Field::optional`] and the builder
+/// methods below; the struct is `#[non_exhaustive]` so that new field
+/// metadata can be added without breaking downstream code.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
 pub struct Field {
     pub name: String,
     pub schema: Schema,
     pub required: bool,
+    /// The field also accepts `null` (it is an `Option<T>` whose `schema` is `T`).
+    #[serde(default)]
+    pub nullable: bool,
+    /// Per-field override of [`TsConfig::optional_style`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional_style: Option<OptionalStyle>,
     pub constraints: Constraints,
 }
 
+/// Value restrictions on a field or [`Schema::Constrained`] node.
+///
+/// Start from `Constraints::default()` and chain the builder methods below;
+/// the struct is `#[non_exhaustive]` so that new kinds of constraint can be
+/// added without breaking downstream code.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
 pub struct Constraints {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min: Option<f64>,
@@ -44,29 +189,63 @@ pub struct Constraints {
     pub max_len: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub one_of: Option<Vec<String>>,
+    /// Regular expression a string value must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Well-known string format, e.g. "date-time", "uuid" or "uri"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
 }
 
 impl Field {
+    /// A required field.
     pub fn new(name: impl Into<String>, schema: Schema) -> Self {
         Self {
             name: name.into(),
             schema,
             required: true,
+            nullable: false,
+            optional_style: None,
             constraints: Constraints::default(),
         }
     }
 
+    /// An optional field that also accepts `null`, like an `Option<T>` field.
     pub fn optional(name: impl Into<String>, schema: Schema) -> Self {
-        Self {
-            name: name.into(),
-            schema,
-            required: false,
-            constraints: Constraints::default(),
-        }
+        Self::new(name, schema).required(false).nullable(true)
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    pub fn optional_style(mut self, style: OptionalStyle) -> Self {
+        self.optional_style = Some(style);
+        self
+    }
+
+    pub fn constraints(mut self, constraints: Constraints) -> Self {
+        self.constraints = constraints;
+        self
     }
 }
 
 impl Schema {
+    /// The schema behind any [`Schema::Shared`] references. Match on this
+    /// rather than on `self` to see a nested type's own schema.
+    pub fn resolved(&self) -> &Schema {
+        match self {
+            Schema::Shared(schema) => schema.resolved(),
+            schema => schema,
+        }
+    }
+
     pub fn type_name(&self) -> &'static str {
         match self {
             Schema::String => "string",
@@ -78,159 +257,400 @@ impl Schema {
             Schema::Array(_) => "table",
             Schema::Object(_) => "table",
             Schema::Enum(_) => "string",
+            Schema::NumericEnum(_) => "integer",
             Schema::Union(_) => "any",
             Schema::Tuple(_) => "table",
             Schema::Ref(_) => "table",
             Schema::Record { .. } => "table",
+            Schema::Ordered(schema) => schema.type_name(),
+            Schema::Shared(schema) => schema.type_name(),
+            Schema::Branded { schema, .. } => schema.type_name(),
+            Schema::Constrained { schema, .. } => schema.type_name(),
+            Schema::Custom { schema, .. } => schema.type_name(),
+            Schema::Bytes(BytesEncoding::Array) => "table",
+            Schema::Bytes(_) => "string",
+        }
+    }
+
+    /// Wrap in `Constrained`, merging with constraints already carried at the
+    /// top (the new ones take precedence).
+    pub fn constrained(self, constraints: Constraints) -> Schema {
+        if constraints.is_empty() {
+            return self;
+        }
+        match self {
+            Schema::Constrained {
+                schema,
+                constraints: existing,
+            } => Schema::Constrained {
+                schema,
+                constraints: constraints.or(&existing),
+            },
+            schema => Schema::Constrained {
+                schema: Box::new(schema),
+                constraints,
+            },
+        }
+    }
+
+    /// Apply `f` to the element schema of arrays and tuples and to the value
+    /// schema of records, looking through wrappers and `Option` unions.
+    pub fn map_items(self, f: &dyn Fn(Schema) -> Schema) -> Schema {
+        match self {
+            Schema::Array(item) => Schema::Array(Box::new(f(*item))),
+            Schema::Tuple(items) => Schema::Tuple(items.into_iter().map(f).collect()),
+            Schema::Record { key, value } => Schema::Record {
+                key,
+                value: Box::new(f(*value)),
+            },
+            other => other.map_wrapped(&|inner| inner.map_items(f)),
+        }
+    }
+
+    /// Apply `f` to the key schema of records, looking through wrappers and
+    /// `Option` unions.
+    pub fn map_keys(self, f: &dyn Fn(Schema) -> Schema) -> Schema {
+        match self {
+            Schema::Record { key, value } => Schema::Record {
+                key: Box::new(f(*key)),
+                value,
+            },
+            other => other.map_wrapped(&|inner| inner.map_keys(f)),
+        }
+    }
+
+    /// Object with every field optional, e.g. for PATCH payloads.
+    pub fn partial(self) -> Schema {
+        self.map_fields(&|fields| {
+            fields
+                .into_iter()
+                .map(|field| Field {
+                    required: false,
+                    ..field
+                })
+                .collect()
+        })
+    }
+
+    /// Object with only the fields named in `names`.
+    pub fn pick(self, names: &[&str]) -> Schema {
+        self.map_fields(&|fields| {
+            fields
+                .into_iter()
+                .filter(|field| names.contains(&field.name.as_str()))
+                .collect()
+        })
+    }
+
+    /// Object without the fields named in `names`.
+    pub fn omit(self, names: &[&str]) -> Schema {
+        self.map_fields(&|fields| {
+            fields
+                .into_iter()
+                .filter(|field| !names.contains(&field.name.as_str()))
+                .collect()
+        })
+    }
+
+    /// Replace `{ Ok: T } | { Err: E }` unions with `T | E`, looking through
+    /// wrappers, `Option` unions and collections.
+    ///
+    /// For APIs that unwrap results before they reach the wire, such as an
+    /// RPC layer resolving `Ok` and rejecting `Err`.
+    pub fn untagged_result(self) -> Schema {
+        match self {
+            Schema::Union(members) if is_result_union(&members) => Schema::Union(
+                members
+                    .into_iter()
+                    .filter_map(|member| match member {
+                        Schema::Object(mut fields) => fields.pop().map(|field| field.schema),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Schema::Array(_) | Schema::Tuple(_) | Schema::Record { .. } => {
+                self.map_items(&Schema::untagged_result)
+            }
+            other => other.map_wrapped(&Schema::untagged_result),
+        }
+    }
+
+    fn map_fields(self, f: &dyn Fn(Vec<Field>) -> Vec<Field>) -> Schema {
+        match self {
+            Schema::Object(fields) => Schema::Object(f(fields)),
+            other => other.map_wrapped(&|inner| inner.map_fields(f)),
+        }
+    }
+
+    fn map_wrapped(self, f: &dyn Fn(Schema) -> Schema) -> Schema {
+        match self {
+            Schema::Branded { name, schema } => Schema::Branded {
+                name,
+                schema: Box::new(f(*schema)),
+            },
+            Schema::Constrained {
+                schema,
+                constraints,
+            } => Schema::Constrained {
+                schema: Box::new(f(*schema)),
+                constraints,
+            },
+            Schema::Custom { ts, schema } => Schema::Custom {
+                ts,
+                schema: Box::new(f(*schema)),
+            },
+            Schema::Ordered(schema) => Schema::Ordered(Box::new(f(*schema))),
+            Schema::Shared(schema) => f(schema.clone()),
+            Schema::Union(members) => Schema::Union(
+                members
+                    .into_iter()
+                    .map(|m| if m == Schema::Null { m } else { f(m) })
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Constraints attached to this schema through `Constrained` nodes,
+    /// looking through `Branded` wrappers. Outer nodes take precedence.
+    pub fn constraints(&self) -> Constraints {
+        match self {
+            Schema::Constrained {
+                schema,
+                constraints,
+            } => constraints.clone().or(&schema.constraints()),
+            Schema::Branded { schema, .. } | Schema::Custom { schema, .. } => schema.constraints(),
+            Schema::Ordered(schema) => schema.constraints(),
+            Schema::Shared(schema) => schema.constraints(),
+            _ => Constraints::default(),
+        }
+    }
+}
+
+impl Constraints {
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = Some(min_len);
+        self
+    }
+
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn one_of<S: Into<String>>(mut self, values: impl IntoIterator<Item = S>) -> Self {
+        self.one_of = Some(values.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Constraints::default()
+    }
+
+    /// `self` with unset values filled in from `fallback`.
+    pub fn or(self, fallback: &Constraints) -> Constraints {
+        Constraints {
+            min: self.min.or(fallback.min),
+            max: self.max.or(fallback.max),
+            min_len: self.min_len.or(fallback.min_len),
+            max_len: self.max_len.or(fallback.max_len),
+            one_of: self.one_of.or_else(|| fallback.one_of.clone()),
+            pattern: self.pattern.or_else(|| fallback.pattern.clone()),
+            format: self.format.or_else(|| fallback.format.clone()),
         }
     }
 }
 
 pub trait SchemaBridge {
-    fn to_ts() -> String;
+    /// Schema of what this type serializes to.
     fn to_schema() -> Schema;
+
+    /// Schema of what this type accepts when deserialized.
+    ///
+    /// Same as [`SchemaBridge::to_schema`] unless fields are only written one
+    /// way, e.g. `skip_serializing`, `default` or split renames.
+    fn to_schema_input() -> Schema {
+        Self::to_schema()
+    }
+
+    /// Schema to embed where this type is nested in another one, e.g. as a
+    /// field or `Vec` element. Defaults to [`SchemaBridge::to_schema`];
+    /// derived types return a [`Schema::Shared`] reference to their
+    /// [cached](SchemaBridge::schema_ref) tree instead of a copy of it.
+    fn shared_schema() -> Schema {
+        Self::to_schema()
+    }
+
+    /// Input side of [`SchemaBridge::shared_schema`].
+    fn shared_schema_input() -> Schema {
+        Self::to_schema_input()
+    }
+
+    /// [`SchemaBridge::to_schema`], built once per type and kept for the rest
+    /// of the process. Use this on hot paths such as request validation.
+    fn schema_ref() -> &'static Schema
+    where
+        Self: 'static,
+    {
+        cache::cached::<Self>(false, || cache::leak(Self::to_schema()))
+    }
+
+    /// [`SchemaBridge::to_schema_input`], built once per type like
+    /// [`SchemaBridge::schema_ref`]. The same reference when both sides match.
+    fn schema_input_ref() -> &'static Schema
+    where
+        Self: 'static,
+    {
+        cache::cached::<Self>(true, || {
+            let output = Self::schema_ref();
+            let input = Self::to_schema_input();
+            if input == *output {
+                output
+            } else {
+                cache::leak(input)
+            }
+        })
+    }
+
+    /// Inline TypeScript type, rendered from [`SchemaBridge::to_schema`] with the
+    /// default [`TsConfig`] so the two outputs cannot diverge.
+    fn to_ts() -> String {
+        render_ts(&Self::to_schema(), &TsConfig::default())
+    }
+
+    /// Top-level declarations emitted for this type by [`export_types!`] under
+    /// `name`, plus `{name}Input` when the input shape differs and any
+    /// [companions](SchemaBridge::companion_declarations).
+    fn ts_declarations(name: &str) -> Vec<Declaration> {
+        let output = Self::to_schema();
+        let input = Self::to_schema_input();
+        let mut declarations = vec![Declaration::new(name, output.clone())];
+        if input != output {
+            declarations.push(Declaration::new(format!("{name}Input"), input));
+        }
+        declarations.extend(Self::companion_declarations());
+        declarations
+    }
+
+    /// Extra named types derived from this one, such as the PATCH payload from
+    /// `#[schema_bridge(partial = "UserPatch")]`.
+    fn companion_declarations() -> Vec<Declaration> {
+        Vec::new()
+    }
 }
 
 // Implement for basic types
 impl SchemaBridge for String {
-    fn to_ts() -> String {
-        "string".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::String
     }
 }
 
 impl SchemaBridge for i32 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for f64 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Number
     }
 }
 
 impl SchemaBridge for bool {
-    fn to_ts() -> String {
-        "boolean".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Boolean
     }
 }
 
 impl SchemaBridge for i8 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for i16 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for i64 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for i128 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for isize {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for u8 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for u16 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for u32 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for u64 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for u128 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for usize {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Integer
     }
 }
 
 impl SchemaBridge for f32 {
-    fn to_ts() -> String {
-        "number".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Number
     }
@@ -238,9 +658,6 @@ impl SchemaBridge for f32 {
 
 // Implement for char
 impl SchemaBridge for char {
-    fn to_ts() -> String {
-        "string".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::String
     }
@@ -248,233 +665,382 @@ impl SchemaBridge for char {
 
 // Implement for unit type
 impl SchemaBridge for () {
-    fn to_ts() -> String {
-        "null".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::Null
     }
 }
 
-impl<T: SchemaBridge> SchemaBridge for Option<T> {
-    fn to_ts() -> String {
-        format!("{} | null", T::to_ts())
+/// `schema | null`, unless `schema` already admits `null`: serde writes both
+/// `None` and `Some(None)` of an `Option<Option<T>>` as `null`.
+fn nullable(schema: Schema) -> Schema {
+    match schema.resolved() {
+        Schema::Null => Schema::Null,
+        Schema::Union(members) if members.contains(&Schema::Null) => schema,
+        _ => Schema::Union(vec![schema, Schema::Null]),
     }
+}
+
+impl<T: SchemaBridge> SchemaBridge for Option<T> {
     fn to_schema() -> Schema {
-        Schema::Union(vec![T::to_schema(), Schema::Null])
+        nullable(T::shared_schema())
+    }
+
+    fn to_schema_input() -> Schema {
+        nullable(T::shared_schema_input())
     }
 }
 
 impl<T: SchemaBridge> SchemaBridge for Vec<T> {
-    fn to_ts() -> String {
-        format!("{}[]", T::to_ts())
-    }
     fn to_schema() -> Schema {
-        Schema::Array(Box::new(T::to_schema()))
+        Schema::Array(Box::new(T::shared_schema()))
+    }
+
+    fn to_schema_input() -> Schema {
+        Schema::Array(Box::new(T::shared_schema_input()))
     }
 }
 
 impl SchemaBridge for PathBuf {
-    fn to_ts() -> String {
-        "string".to_string()
-    }
     fn to_schema() -> Schema {
         Schema::String
     }
 }
 
-impl<K, V> SchemaBridge for HashMap<K, V>
-where
-    K: SchemaBridge,
-    V: SchemaBridge,
-{
-    fn to_ts() -> String {
-        format!("Record<{}, {}>", K::to_ts(), V::to_ts())
+// Dynamic JSON, e.g. metadata or extension fields
+impl SchemaBridge for serde_json::Value {
+    fn to_schema() -> Schema {
+        Schema::Any
     }
+}
+
+impl SchemaBridge for serde_json::Map<String, serde_json::Value> {
     fn to_schema() -> Schema {
         Schema::Record {
-            key: Box::new(K::to_schema()),
-            value: Box::new(V::to_schema()),
+            key: Box::new(Schema::String),
+            value: Box::new(Schema::Any),
         }
     }
 }
 
-impl<K, V> SchemaBridge for BTreeMap<K, V>
-where
-    K: SchemaBridge,
-    V: SchemaBridge,
-{
-    fn to_ts() -> String {
-        format!("Record<{}, {}>", K::to_ts(), V::to_ts())
-    }
+#[cfg(feature = "raw_value")]
+impl SchemaBridge for serde_json::value::RawValue {
     fn to_schema() -> Schema {
-        Schema::Record {
-            key: Box::new(K::to_schema()),
-            value: Box::new(V::to_schema()),
-        }
+        Schema::Any
     }
 }
 
-impl<T: SchemaBridge> SchemaBridge for HashSet<T> {
-    fn to_ts() -> String {
-        format!("{}[]", T::to_ts())
+/// `Record<K, V>` with the key as serde writes it in key position.
+fn record<K: SchemaKey>(value: Schema) -> Schema {
+    Schema::Record {
+        key: Box::new(K::to_key_schema()),
+        value: Box::new(value),
     }
+}
+
+// Any hasher: `S` only affects lookups, not the serialized form
+impl<K: SchemaKey, V: SchemaBridge, S> SchemaBridge for HashMap<K, V, S> {
     fn to_schema() -> Schema {
-        Schema::Array(Box::new(T::to_schema()))
+        record::<K>(V::shared_schema())
+    }
+
+    fn to_schema_input() -> Schema {
+        record::<K>(V::shared_schema_input())
     }
 }
 
-impl<T: SchemaBridge> SchemaBridge for BTreeSet<T> {
-    fn to_ts() -> String {
-        format!("{}[]", T::to_ts())
+impl<K: SchemaKey, V: SchemaBridge> SchemaBridge for BTreeMap<K, V> {
+    fn to_schema() -> Schema {
+        record::<K>(V::shared_schema())
     }
+
+    fn to_schema_input() -> Schema {
+        record::<K>(V::shared_schema_input())
+    }
+}
+
+impl<T: SchemaBridge, S> SchemaBridge for HashSet<T, S> {
     fn to_schema() -> Schema {
-        Schema::Array(Box::new(T::to_schema()))
+        Schema::Array(Box::new(T::shared_schema()))
+    }
+
+    fn to_schema_input() -> Schema {
+        Schema::Array(Box::new(T::shared_schema_input()))
     }
 }
 
-impl<T: SchemaBridge> SchemaBridge for Box<T> {
-    fn to_ts() -> String {
-        T::to_ts()
+impl<T: SchemaBridge> SchemaBridge for BTreeSet<T> {
+    fn to_schema() -> Schema {
+        Schema::Array(Box::new(T::shared_schema()))
+    }
+
+    fn to_schema_input() -> Schema {
+        Schema::Array(Box::new(T::shared_schema_input()))
     }
+}
+
+impl<T: SchemaBridge + ?Sized> SchemaBridge for Box<T> {
     fn to_schema() -> Schema {
         T::to_schema()
     }
-}
 
-impl<T: SchemaBridge> SchemaBridge for Rc<T> {
-    fn to_ts() -> String {
-        T::to_ts()
+    fn to_schema_input() -> Schema {
+        T::to_schema_input()
     }
+}
+
+impl<T: SchemaBridge + ?Sized> SchemaBridge for Rc<T> {
     fn to_schema() -> Schema {
         T::to_schema()
     }
-}
 
-impl<T: SchemaBridge> SchemaBridge for Arc<T> {
-    fn to_ts() -> String {
-        T::to_ts()
+    fn to_schema_input() -> Schema {
+        T::to_schema_input()
     }
+}
+
+impl<T: SchemaBridge + ?Sized> SchemaBridge for Arc<T> {
     fn to_schema() -> Schema {
         T::to_schema()
     }
+
+    fn to_schema_input() -> Schema {
+        T::to_schema_input()
+    }
+}
+
+/// serde's externally tagged encoding: `{ "Ok": T }` or `{ "Err": E }`.
+/// See [`Schema::untagged_result`] for APIs that flatten results.
+fn result(ok: Schema, err: Schema) -> Schema {
+    Schema::Union(vec![
+        Schema::Object(vec![Field::new("Ok", ok)]),
+        Schema::Object(vec![Field::new("Err", err)]),
+    ])
+}
+
+fn is_result_union(members: &[Schema]) -> bool {
+    let tagged = |member: &Schema, tag: &str| matches!(member.resolved(), Schema::Object(fields) if fields.len() == 1 && fields[0].name == tag);
+    matches!(members, [ok, err] if tagged(ok, "Ok") && tagged(err, "Err"))
 }
 
 impl<T: SchemaBridge, E: SchemaBridge> SchemaBridge for Result<T, E> {
-    fn to_ts() -> String {
-        format!("{} | {}", T::to_ts(), E::to_ts())
+    fn to_schema() -> Schema {
+        result(T::shared_schema(), E::shared_schema())
+    }
+
+    fn to_schema_input() -> Schema {
+        result(T::shared_schema_input(), E::shared_schema_input())
     }
+}
+
+impl SchemaBridge for str {
     fn to_schema() -> Schema {
-        Schema::Union(vec![T::to_schema(), E::to_schema()])
+        Schema::String
     }
 }
 
-// Tuple implementations
-impl<T: SchemaBridge> SchemaBridge for (T,) {
-    fn to_ts() -> String {
-        format!("[{}]", T::to_ts())
+impl SchemaBridge for Path {
+    fn to_schema() -> Schema {
+        Schema::String
     }
+}
+
+// References and wrappers serialize as the value they hold
+impl<T: SchemaBridge + ?Sized> SchemaBridge for &T {
     fn to_schema() -> Schema {
-        Schema::Tuple(vec![T::to_schema()])
+        T::to_schema()
+    }
+
+    fn to_schema_input() -> Schema {
+        T::to_schema_input()
     }
 }
 
-impl<T1: SchemaBridge, T2: SchemaBridge> SchemaBridge for (T1, T2) {
-    fn to_ts() -> String {
-        format!("[{}, {}]", T1::to_ts(), T2::to_ts())
+impl<T: SchemaBridge + ?Sized> SchemaBridge for &mut T {
+    fn to_schema() -> Schema {
+        T::to_schema()
+    }
+
+    fn to_schema_input() -> Schema {
+        T::to_schema_input()
     }
+}
+
+impl<T: SchemaBridge + ToOwned + ?Sized> SchemaBridge for Cow<'_, T> {
     fn to_schema() -> Schema {
-        Schema::Tuple(vec![T1::to_schema(), T2::to_schema()])
+        T::to_schema()
+    }
+
+    fn to_schema_input() -> Schema {
+        T::to_schema_input()
     }
 }
 
-impl<T1: SchemaBridge, T2: SchemaBridge, T3: SchemaBridge> SchemaBridge for (T1, T2, T3) {
-    fn to_ts() -> String {
-        format!("[{}, {}, {}]", T1::to_ts(), T2::to_ts(), T3::to_ts())
+macro_rules! impl_transparent {
+    ($($wrapper:ident),+) => {
+        $(impl<T: SchemaBridge> SchemaBridge for $wrapper<T> {
+            fn to_schema() -> Schema {
+                T::to_schema()
+            }
+
+            fn to_schema_input() -> Schema {
+                T::to_schema_input()
+            }
+        })+
+    };
+}
+
+impl_transparent!(Reverse, Cell, RefCell, Mutex, RwLock);
+
+// serde writes PhantomData as unit
+impl<T: ?Sized> SchemaBridge for PhantomData<T> {
+    fn to_schema() -> Schema {
+        Schema::Null
     }
+}
+
+macro_rules! impl_sequence {
+    ($($seq:ident),+) => {
+        $(impl<T: SchemaBridge> SchemaBridge for $seq<T> {
+            fn to_schema() -> Schema {
+                Schema::Array(Box::new(T::shared_schema()))
+            }
+
+            fn to_schema_input() -> Schema {
+                Schema::Array(Box::new(T::shared_schema_input()))
+            }
+        })+
+    };
+}
+
+impl_sequence!(VecDeque, LinkedList, BinaryHeap);
+
+impl<T: SchemaBridge> SchemaBridge for [T] {
     fn to_schema() -> Schema {
-        Schema::Tuple(vec![T1::to_schema(), T2::to_schema(), T3::to_schema()])
+        Schema::Array(Box::new(T::shared_schema()))
+    }
+
+    fn to_schema_input() -> Schema {
+        Schema::Array(Box::new(T::shared_schema_input()))
     }
 }
 
-impl<T1: SchemaBridge, T2: SchemaBridge, T3: SchemaBridge, T4: SchemaBridge> SchemaBridge
-    for (T1, T2, T3, T4)
-{
-    fn to_ts() -> String {
-        format!(
-            "[{}, {}, {}, {}]",
-            T1::to_ts(),
-            T2::to_ts(),
-            T3::to_ts(),
-            T4::to_ts()
-        )
-    }
-    fn to_schema() -> Schema {
-        Schema::Tuple(vec![
-            T1::to_schema(),
-            T2::to_schema(),
-            T3::to_schema(),
-            T4::to_schema(),
-        ])
+/// Arrays up to this length render as TS tuples; longer ones as arrays with
+/// an exact length constraint.
+const MAX_TUPLE_ARRAY_LEN: usize = 16;
+
+fn fixed_array(item: Schema, len: usize) -> Schema {
+    if len <= MAX_TUPLE_ARRAY_LEN {
+        Schema::Tuple(vec![item; len])
+    } else {
+        Schema::Array(Box::new(item)).constrained(Constraints {
+            min_len: Some(len),
+            max_len: Some(len),
+            ..Default::default()
+        })
     }
 }
 
-impl<T1: SchemaBridge, T2: SchemaBridge, T3: SchemaBridge, T4: SchemaBridge, T5: SchemaBridge>
-    SchemaBridge for (T1, T2, T3, T4, T5)
-{
-    fn to_ts() -> String {
-        format!(
-            "[{}, {}, {}, {}, {}]",
-            T1::to_ts(),
-            T2::to_ts(),
-            T3::to_ts(),
-            T4::to_ts(),
-            T5::to_ts()
-        )
-    }
-    fn to_schema() -> Schema {
-        Schema::Tuple(vec![
-            T1::to_schema(),
-            T2::to_schema(),
-            T3::to_schema(),
-            T4::to_schema(),
-            T5::to_schema(),
+impl<T: SchemaBridge, const N: usize> SchemaBridge for [T; N] {
+    fn to_schema() -> Schema {
+        fixed_array(T::shared_schema(), N)
+    }
+
+    fn to_schema_input() -> Schema {
+        fixed_array(T::shared_schema_input(), N)
+    }
+}
+
+macro_rules! impl_non_zero {
+    ($min:expr => $($ty:ident),+) => {
+        $(impl SchemaBridge for $ty {
+            fn to_schema() -> Schema {
+                Schema::Integer.constrained(Constraints {
+                    min: $min,
+                    ..Default::default()
+                })
+            }
+        })+
+    };
+}
+
+impl_non_zero!(Some(1.0) => NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+// Zero sits inside the range, so only the integer type is expressible
+impl_non_zero!(None => NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+
+// Structs as written by serde's std impls
+impl SchemaBridge for Duration {
+    fn to_schema() -> Schema {
+        Schema::Object(vec![
+            Field::new("secs", Schema::Integer),
+            Field::new("nanos", Schema::Integer),
         ])
     }
 }
 
-impl<
-        T1: SchemaBridge,
-        T2: SchemaBridge,
-        T3: SchemaBridge,
-        T4: SchemaBridge,
-        T5: SchemaBridge,
-        T6: SchemaBridge,
-    > SchemaBridge for (T1, T2, T3, T4, T5, T6)
-{
-    fn to_ts() -> String {
-        format!(
-            "[{}, {}, {}, {}, {}, {}]",
-            T1::to_ts(),
-            T2::to_ts(),
-            T3::to_ts(),
-            T4::to_ts(),
-            T5::to_ts(),
-            T6::to_ts()
-        )
-    }
-    fn to_schema() -> Schema {
-        Schema::Tuple(vec![
-            T1::to_schema(),
-            T2::to_schema(),
-            T3::to_schema(),
-            T4::to_schema(),
-            T5::to_schema(),
-            T6::to_schema(),
+impl SchemaBridge for SystemTime {
+    fn to_schema() -> Schema {
+        Schema::Object(vec![
+            Field::new("secs_since_epoch", Schema::Integer),
+            Field::new("nanos_since_epoch", Schema::Integer),
         ])
     }
 }
 
+// Addresses serialize as strings in human-readable formats such as JSON
+macro_rules! impl_address {
+    ($($ty:ident => $format:expr),+ $(,)?) => {
+        $(impl SchemaBridge for $ty {
+            fn to_schema() -> Schema {
+                Schema::String.constrained(Constraints {
+                    format: $format.map(str::to_string),
+                    ..Default::default()
+                })
+            }
+        })+
+    };
+}
+
+impl_address!(
+    IpAddr => None::<&str>,
+    Ipv4Addr => Some("ipv4"),
+    Ipv6Addr => Some("ipv6"),
+    SocketAddr => None::<&str>,
+    SocketAddrV4 => None::<&str>,
+    SocketAddrV6 => None::<&str>,
+);
+
+// Tuple implementations, up to serde's limit of 16 elements
+macro_rules! impl_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: SchemaBridge),+> SchemaBridge for ($($name,)+) {
+            fn to_schema() -> Schema {
+                Schema::Tuple(vec![$($name::shared_schema()),+])
+            }
+
+            fn to_schema_input() -> Schema {
+                Schema::Tuple(vec![$($name::shared_schema_input()),+])
+            }
+        }
+    };
+}
+
+impl_tuple!(T1);
+impl_tuple!(T1 T2);
+impl_tuple!(T1 T2 T3);
+impl_tuple!(T1 T2 T3 T4);
+impl_tuple!(T1 T2 T3 T4 T5);
+impl_tuple!(T1 T2 T3 T4 T5 T6);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15);
+impl_tuple!(T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16);
+
 // Helper to generate the full TS file content
 pub fn generate_ts_file(types: Vec<(&str, String)>) -> String {
     let mut content = String::new();
@@ -494,14 +1060,21 @@ pub fn export_to_file(types: Vec<(&str, String)>, path: &str) -> std::io::Result
 }
 
 /// Macro to easily export types to a file
+///
+/// Pass `config = ...` first to render with a [`TsConfig`]:
+/// `export_types!(config = TsConfig::new().pretty(true), "bindings.ts", User)`.
 #[macro_export]
 macro_rules! export_types {
-    ($path:expr, $($name:ident),+ $(,)?) => {{
-        let types = vec![
-            $((stringify!($name), $name::to_ts()),)+
-        ];
-        $crate::export_to_file(types, $path)
+    (config = $config:expr, $path:expr, $($name:ident),+ $(,)?) => {{
+        let mut declarations = ::std::vec::Vec::new();
+        $(declarations.extend(
+            <$name as $crate::SchemaBridge>::ts_declarations(stringify!($name)),
+        );)+
+        $crate::export_to_file_with(declarations, $path, &$config)
     }};
+    ($path:expr, $($name:ident),+ $(,)?) => {
+        $crate::export_types!(config = $crate::TsConfig::default(), $path, $($name),+)
+    };
 }
 
 // --- mlua integration ---
@@ -518,7 +1091,7 @@ mod lua {
         /// For `Schema::Object`, produces a table where each key maps to
         /// either a type-name string (shorthand) or a full constraint table.
         pub fn to_lua_table(&self, lua: &Lua) -> LuaResult<LuaValue> {
-            match self {
+            match self.resolved() {
                 Schema::Object(fields) => {
                     let t = lua.create_table()?;
                     for field in fields {
@@ -536,11 +1109,15 @@ mod lua {
     }
 
     fn field_to_lua_value(lua: &Lua, field: &Field) -> LuaResult<LuaValue> {
-        let has_constraints = field.constraints.min.is_some()
-            || field.constraints.max.is_some()
-            || field.constraints.min_len.is_some()
-            || field.constraints.max_len.is_some()
-            || field.constraints.one_of.is_some();
+        // Field-level constraints refine those carried by the field's type
+        let constraints = field.constraints.clone().or(&field.schema.constraints());
+        let (items, keys) = element_schemas(&field.schema);
+        let items = items.filter(|s| !s.constraints().is_empty());
+        let keys = keys.filter(|s| !s.constraints().is_empty());
+        let has_constraints = !constraints.is_empty()
+            || matches!(field.schema.resolved(), Schema::NumericEnum(_))
+            || items.is_some()
+            || keys.is_some();
 
         // Use shorthand format when: not required AND no constraints
         // (shorthand means the field is optional with just a type check)
@@ -557,28 +1134,90 @@ mod lua {
         if field.required {
             t.set("required", true)?;
         }
+        set_constraints(lua, &t, &constraints, &field.schema)?;
+        if let Some(item) = items {
+            t.set("items", element_to_lua(lua, item)?)?;
+        }
+        if let Some(key) = keys {
+            t.set("keys", element_to_lua(lua, key)?)?;
+        }
+
+        Ok(LuaValue::Table(t))
+    }
+
+    /// Element spec for `items` / `keys`: `{ type = "...", <constraints> }`
+    fn element_to_lua(lua: &Lua, schema: &Schema) -> LuaResult<LuaTable> {
+        let t = lua.create_table()?;
+        t.set("type", schema.type_name())?;
+        set_constraints(lua, &t, &schema.constraints(), schema)?;
+        let (items, keys) = element_schemas(schema);
+        if let Some(item) = items.filter(|s| !s.constraints().is_empty()) {
+            t.set("items", element_to_lua(lua, item)?)?;
+        }
+        if let Some(key) = keys.filter(|s| !s.constraints().is_empty()) {
+            t.set("keys", element_to_lua(lua, key)?)?;
+        }
+        Ok(t)
+    }
+
+    /// Item (array element or record value) and key schemas, looking through
+    /// wrappers and `Option` unions.
+    fn element_schemas(schema: &Schema) -> (Option<&Schema>, Option<&Schema>) {
+        match schema {
+            Schema::Array(item) => (Some(item), None),
+            Schema::Record { key, value, .. } => (Some(value), Some(key)),
+            Schema::Branded { schema, .. }
+            | Schema::Constrained { schema, .. }
+            | Schema::Custom { schema, .. }
+            | Schema::Ordered(schema) => element_schemas(schema),
+            Schema::Shared(schema) => element_schemas(schema),
+            Schema::Union(members) => match members.as_slice() {
+                [inner, Schema::Null] | [Schema::Null, inner] => element_schemas(inner),
+                _ => (None, None),
+            },
+            _ => (None, None),
+        }
+    }
 
-        if let Some(min) = field.constraints.min {
+    fn set_constraints(
+        lua: &Lua,
+        t: &LuaTable,
+        constraints: &Constraints,
+        schema: &Schema,
+    ) -> LuaResult<()> {
+        if let Some(min) = constraints.min {
             t.set("min", min)?;
         }
-        if let Some(max) = field.constraints.max {
+        if let Some(max) = constraints.max {
             t.set("max", max)?;
         }
-        if let Some(min_len) = field.constraints.min_len {
+        if let Some(min_len) = constraints.min_len {
             t.set("min_len", min_len as i64)?;
         }
-        if let Some(max_len) = field.constraints.max_len {
+        if let Some(max_len) = constraints.max_len {
             t.set("max_len", max_len as i64)?;
         }
-        if let Some(ref one_of) = field.constraints.one_of {
+        if let Some(ref pattern) = constraints.pattern {
+            t.set("pattern", pattern.as_str())?;
+        }
+        if let Some(ref format) = constraints.format {
+            t.set("format", format.as_str())?;
+        }
+        if let Some(ref one_of) = constraints.one_of {
             let arr = lua.create_table()?;
             for (i, val) in one_of.iter().enumerate() {
                 arr.set(i + 1, val.as_str())?;
             }
             t.set("one_of", arr)?;
+        } else if let Schema::NumericEnum(values) = schema.resolved() {
+            // Integer type alone would accept any number
+            let arr = lua.create_table()?;
+            for (i, val) in values.iter().enumerate() {
+                arr.set(i + 1, *val)?;
+            }
+            t.set("one_of", arr)?;
         }
-
-        Ok(LuaValue::Table(t))
+        Ok(())
     }
 }
 
@@ -628,6 +1267,25 @@ mod tests {
         assert_eq!(Option::<Vec::<String>>::to_ts(), "string[] | null");
     }
 
+    #[test]
+    fn test_vec_of_option_to_ts() {
+        assert_eq!(Vec::<Option<String>>::to_ts(), "(string | null)[]");
+    }
+
+    #[test]
+    fn test_to_ts_defaults_to_rendered_schema() {
+        struct Point;
+        impl SchemaBridge for Point {
+            fn to_schema() -> Schema {
+                Schema::Object(vec![
+                    Field::new("x", Schema::Number),
+                    Field::new("y", Schema::Number),
+                ])
+            }
+        }
+        assert_eq!(Point::to_ts(), "{ x: number; y: number; }");
+    }
+
     #[test]
     fn test_generate_ts_file() {
         let types = vec![
@@ -685,12 +1343,25 @@ mod tests {
     fn test_hashmap_to_schema() {
         let schema = HashMap::<String, i32>::to_schema();
         assert!(matches!(schema, Schema::Record { .. }));
-        if let Schema::Record { key, value } = schema {
+        if let Schema::Record { key, value, .. } = schema {
             assert_eq!(*key, Schema::String);
             assert_eq!(*value, Schema::Integer);
         }
     }
 
+    #[test]
+    fn test_custom_hasher() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+
+        type Fast = BuildHasherDefault<DefaultHasher>;
+        assert_eq!(
+            HashMap::<String, i32, Fast>::to_schema(),
+            HashMap::<String, i32>::to_schema()
+        );
+        assert_eq!(HashSet::<u8, Fast>::to_ts(), "number[]");
+    }
+
     #[test]
     fn test_nested_hashmap() {
         assert_eq!(
@@ -778,8 +1449,14 @@ mod tests {
     // Test Result
     #[test]
     fn test_result_to_ts() {
-        assert_eq!(Result::<String, String>::to_ts(), "string | string");
-        assert_eq!(Result::<i32, String>::to_ts(), "number | string");
+        assert_eq!(
+            Result::<String, String>::to_ts(),
+            "{ Ok: string; } | { Err: string; }"
+        );
+        assert_eq!(
+            Result::<i32, String>::to_ts(),
+            "{ Ok: number; } | { Err: string; }"
+        );
     }
 
     #[test]
@@ -788,8 +1465,14 @@ mod tests {
         assert!(matches!(schema, Schema::Union(_)));
         if let Schema::Union(types) = schema {
             assert_eq!(types.len(), 2);
-            assert_eq!(types[0], Schema::String);
-            assert_eq!(types[1], Schema::Integer);
+            assert_eq!(
+                types[0],
+                Schema::Object(vec![Field::new("Ok", Schema::String)])
+            );
+            assert_eq!(
+                types[1],
+                Schema::Object(vec![Field::new("Err", Schema::Integer)])
+            );
         }
     }
 
@@ -819,6 +1502,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tuple_16() {
+        type Sample = (
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            String,
+        );
+        let Schema::Tuple(items) = Sample::to_schema() else {
+            panic!("Expected Tuple schema");
+        };
+        assert_eq!(items.len(), 16);
+        assert_eq!(items[15], Schema::String);
+    }
+
     #[test]
     fn test_tuple_schema() {
         let schema = <(String, i32)>::to_schema();
@@ -884,6 +1594,167 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_constrained_schema_constraints() {
+        let schema = Schema::Branded {
+            name: "Age".into(),
+            schema: Box::new(Schema::Constrained {
+                schema: Box::new(Schema::Integer),
+                constraints: Constraints {
+                    min: Some(0.0),
+                    ..Default::default()
+                },
+            }),
+        };
+        assert_eq!(schema.type_name(), "integer");
+        assert_eq!(schema.constraints().min, Some(0.0));
+        assert!(Schema::Integer.constraints().is_empty());
+    }
+
+    #[test]
+    fn test_map_items_and_keys() {
+        let len = Constraints {
+            max_len: Some(32),
+            ..Default::default()
+        };
+        let tags = Vec::<String>::to_schema().map_items(&|s| s.constrained(len.clone()));
+        assert_eq!(
+            tags,
+            Schema::Array(Box::new(Schema::Constrained {
+                schema: Box::new(Schema::String),
+                constraints: len.clone(),
+            }))
+        );
+
+        let optional =
+            Option::<HashMap<String, i32>>::to_schema().map_keys(&|s| s.constrained(len.clone()));
+        let Schema::Union(members) = optional else {
+            panic!("Expected Union");
+        };
+        let Schema::Record { key, value, .. } = &members[0] else {
+            panic!("Expected Record");
+        };
+        assert_eq!(key.constraints(), len);
+        assert_eq!(**value, Schema::Integer);
+        assert_eq!(members[1], Schema::Null);
+    }
+
+    #[test]
+    fn test_partial_pick_omit() {
+        let user = Schema::Object(vec![
+            Field::new("id", Schema::Integer),
+            Field::new("name", Schema::String),
+            Field::optional("bio", Schema::String),
+        ]);
+        let names = |schema: Schema| match schema {
+            Schema::Object(fields) => fields
+                .into_iter()
+                .map(|f| (f.name, f.required))
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected Object"),
+        };
+
+        assert_eq!(
+            names(user.clone().omit(&["id"]).partial()),
+            [("name".to_string(), false), ("bio".to_string(), false)]
+        );
+        assert_eq!(
+            names(user.clone().pick(&["id", "name"])),
+            [("id".to_string(), true), ("name".to_string(), true)]
+        );
+
+        let constrained = user.constrained(Constraints {
+            max_len: Some(3),
+            ..Default::default()
+        });
+        let Schema::Constrained { schema, .. } = constrained.pick(&["id"]) else {
+            panic!("Expected Constrained");
+        };
+        assert_eq!(names(*schema), [("id".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_std_special_types() {
+        assert_eq!(<&str>::to_schema(), Schema::String);
+        assert_eq!(Cow::<'_, str>::to_schema(), Schema::String);
+        assert_eq!(Mutex::<Vec<u8>>::to_schema(), Vec::<u8>::to_schema());
+        assert_eq!(PhantomData::<String>::to_schema(), Schema::Null);
+        assert_eq!(VecDeque::<bool>::to_ts(), "boolean[]");
+        assert_eq!(NonZeroU32::to_schema().constraints().min, Some(1.0));
+        assert_eq!(NonZeroI32::to_schema(), Schema::Integer);
+        assert_eq!(
+            Ipv4Addr::to_schema().constraints().format.as_deref(),
+            Some("ipv4")
+        );
+        assert_eq!(Duration::to_ts(), "{ secs: number; nanos: number; }");
+    }
+
+    #[test]
+    fn test_untagged_result() {
+        assert_eq!(
+            Result::<u32, String>::to_schema().untagged_result(),
+            Schema::Union(vec![Schema::Integer, Schema::String])
+        );
+        let nested = Vec::<Option<Result<bool, String>>>::to_schema().untagged_result();
+        assert_eq!(
+            render_ts(&nested, &TsConfig::default()),
+            "(boolean | string | null)[]"
+        );
+    }
+
+    #[test]
+    fn test_nested_option_does_not_repeat_null() {
+        assert_eq!(Option::<Option<u8>>::to_schema(), Option::<u8>::to_schema());
+        assert_eq!(Option::<()>::to_schema(), Schema::Null);
+    }
+
+    #[test]
+    fn test_fixed_arrays() {
+        assert_eq!(<[f64; 3]>::to_ts(), "[number, number, number]");
+        assert_eq!(<[u8; 0]>::to_schema(), Schema::Tuple(vec![]));
+
+        let hash = <[u8; 32]>::to_schema();
+        assert_eq!(<[u8; 32]>::to_ts(), "number[]");
+        let c = hash.constraints();
+        assert_eq!((c.min_len, c.max_len), (Some(32), Some(32)));
+    }
+
+    #[test]
+    fn test_constrained_merges() {
+        let schema = Schema::String
+            .constrained(Constraints {
+                min_len: Some(1),
+                max_len: Some(10),
+                ..Default::default()
+            })
+            .constrained(Constraints {
+                max_len: Some(5),
+                ..Default::default()
+            });
+        let c = schema.constraints();
+        assert_eq!((c.min_len, c.max_len), (Some(1), Some(5)));
+        assert_eq!(
+            Schema::String.constrained(Constraints::default()),
+            Schema::String
+        );
+    }
+
+    #[test]
+    fn test_constraints_or() {
+        let field = Constraints {
+            max: Some(10.0),
+            ..Default::default()
+        };
+        let ty = Constraints {
+            min: Some(1.0),
+            max: Some(100.0),
+            ..Default::default()
+        };
+        let merged = field.or(&ty);
+        assert_eq!(merged.min, Some(1.0));
+        assert_eq!(merged.max, Some(10.0));
+    }
+
     #[test]
     fn test_constraints_with_values() {
         let c = Constraints {
@@ -892,6 +1763,8 @@ mod tests {
             min_len: None,
             max_len: Some(255),
             one_of: None,
+            pattern: None,
+            format: None,
         };
         assert_eq!(c.min, Some(0.0));
         assert_eq!(c.max, Some(100.0));
@@ -934,6 +1807,8 @@ mod lua_tests {
             name: "age".into(),
             schema: Schema::Integer,
             required: true,
+            nullable: false,
+            optional_style: None,
             constraints: Constraints {
                 min: Some(0.0),
                 max: Some(150.0),
@@ -960,6 +1835,8 @@ mod lua_tests {
             name: "status".into(),
             schema: Schema::String,
             required: true,
+            nullable: false,
+            optional_style: None,
             constraints: Constraints {
                 one_of: Some(vec!["active".into(), "inactive".into()]),
                 ..Default::default()
@@ -977,6 +1854,88 @@ mod lua_tests {
         assert_eq!(v2, "inactive");
     }
 
+    #[test]
+    fn to_lua_table_numeric_enum_one_of() {
+        let lua = Lua::new();
+        let schema = Schema::Object(vec![Field::new("code", Schema::NumericEnum(vec![1, 2, 4]))]);
+
+        let value = schema.to_lua_table(&lua).unwrap();
+        let table = value.as_table().unwrap();
+
+        let code: LuaTable = table.get("code").unwrap();
+        let code_type: String = code.get("type").unwrap();
+        assert_eq!(code_type, "integer");
+        let one_of: LuaTable = code.get("one_of").unwrap();
+        let values: Vec<i64> = one_of.sequence_values().collect::<LuaResult<_>>().unwrap();
+        assert_eq!(values, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn to_lua_table_inherits_type_constraints() {
+        let lua = Lua::new();
+        let name = Schema::Constrained {
+            schema: Box::new(Schema::String),
+            constraints: Constraints {
+                min_len: Some(1),
+                max_len: Some(64),
+                ..Default::default()
+            },
+        };
+        let mut field = Field::optional("name", name);
+        field.constraints.max_len = Some(32);
+        let schema = Schema::Object(vec![field]);
+
+        let value = schema.to_lua_table(&lua).unwrap();
+        let table = value.as_table().unwrap();
+
+        let name: LuaTable = table.get("name").unwrap();
+        let name_type: String = name.get("type").unwrap();
+        assert_eq!(name_type, "string");
+        let min_len: i64 = name.get("min_len").unwrap();
+        assert_eq!(min_len, 1);
+        let max_len: i64 = name.get("max_len").unwrap();
+        assert_eq!(max_len, 32);
+    }
+
+    #[test]
+    fn to_lua_table_element_constraints() {
+        let lua = Lua::new();
+        let tag = Schema::String.constrained(Constraints {
+            max_len: Some(32),
+            ..Default::default()
+        });
+        let key = Schema::String.constrained(Constraints {
+            pattern: Some("^[a-z]+$".to_string()),
+            ..Default::default()
+        });
+        let schema = Schema::Object(vec![
+            Field::optional("tags", Schema::Array(Box::new(tag))),
+            Field::new(
+                "scores",
+                Schema::Record {
+                    key: Box::new(key),
+                    value: Box::new(Schema::Integer),
+                },
+            ),
+        ]);
+
+        let value = schema.to_lua_table(&lua).unwrap();
+        let table = value.as_table().unwrap();
+
+        let tags: LuaTable = table.get("tags").unwrap();
+        let items: LuaTable = tags.get("items").unwrap();
+        let item_type: String = items.get("type").unwrap();
+        assert_eq!(item_type, "string");
+        let max_len: i64 = items.get("max_len").unwrap();
+        assert_eq!(max_len, 32);
+
+        let scores: LuaTable = table.get("scores").unwrap();
+        let keys: LuaTable = scores.get("keys").unwrap();
+        let pattern: String = keys.get("pattern").unwrap();
+        assert_eq!(pattern, "^[a-z]+$");
+        assert!(scores.get::<LuaValue>("items").unwrap().is_nil());
+    }
+
     #[test]
     fn to_lua_table_non_object_returns_string() {
         let lua = Lua::new();
@@ -0,0 +1,896 @@
+//! TypeScript rendering of [`Schema`] trees.
+//!
+//! [`TsConfig`] controls the shape of the generated file: layout, declaration
+//! keyword, modifiers and punctuation, so the output can satisfy a project's
+//! lint rules without post-processing.
+
+use crate::{BytesEncoding, Field, Schema};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_BANNER: &str = "// This file is auto-generated by schema-bridge";
+
+const JSON_VALUE: &str = "JsonValue";
+
+/// How object types are declared at the top level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeclarationStyle {
+    /// `export type User = { ... };`
+    #[default]
+    TypeAlias,
+    /// `export interface User { ... }` (object types only; everything else
+    /// falls back to a type alias)
+    Interface,
+}
+
+/// Quote character used for string literal types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// `'Active'`
+    #[default]
+    Single,
+    /// `"Active"`
+    Double,
+}
+
+impl QuoteStyle {
+    fn quote(self, value: &str) -> String {
+        let q = match self {
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Double => '"',
+        };
+        let mut out = String::with_capacity(value.len() + 2);
+        out.push(q);
+        for ch in value.chars() {
+            if ch == q || ch == '\\' {
+                out.push('\\');
+            }
+            out.push(ch);
+        }
+        out.push(q);
+        out
+    }
+}
+
+/// How a nullable field that may also be absent (an `Option<T>` field) is
+/// rendered as an object member.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionalStyle {
+    /// `field?: T`
+    Optional,
+    /// `field?: T | null`
+    OptionalNullable,
+    /// `field: T | null`
+    #[default]
+    Nullable,
+}
+
+/// How [`Schema::Any`] (e.g. a `serde_json::Value`) is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnyStyle {
+    /// `any`
+    #[default]
+    Any,
+    /// `unknown`
+    Unknown,
+    /// `JsonValue`, a recursive alias for any JSON value that
+    /// [`generate_ts_file_with`] declares when it is used
+    JsonValue,
+}
+
+/// Options for generating TypeScript output.
+///
+/// The default configuration reproduces the classic single-line output:
+///
+/// ```text
+/// // This file is auto-generated by schema-bridge
+///
+/// export type User = { name: string; age: number; };
+/// ```
+///
+/// ```
+/// use schema_bridge_core::{DeclarationStyle, QuoteStyle, TsConfig};
+///
+/// let config = TsConfig::new()
+///     .pretty(true)
+///     .indent(4)
+///     .declaration_style(DeclarationStyle::Interface)
+///     .quote_style(QuoteStyle::Double)
+///     .header("/* eslint-disable */");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TsConfig {
+    pretty: bool,
+    indent: usize,
+    declaration_style: DeclarationStyle,
+    readonly: bool,
+    declare: bool,
+    semicolons: bool,
+    quote_style: QuoteStyle,
+    optional_style: OptionalStyle,
+    any_style: AnyStyle,
+    banner: Option<String>,
+    headers: Vec<String>,
+}
+
+impl Default for TsConfig {
+    fn default() -> Self {
+        Self {
+            pretty: false,
+            indent: 2,
+            declaration_style: DeclarationStyle::default(),
+            readonly: false,
+            declare: false,
+            semicolons: true,
+            quote_style: QuoteStyle::default(),
+            optional_style: OptionalStyle::default(),
+            any_style: AnyStyle::default(),
+            banner: Some(DEFAULT_BANNER.to_string()),
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl TsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render object types over multiple lines, one member per line.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Number of spaces per indentation level in pretty mode (default 2).
+    pub fn indent(mut self, spaces: usize) -> Self {
+        self.indent = spaces;
+        self
+    }
+
+    pub fn declaration_style(mut self, style: DeclarationStyle) -> Self {
+        self.declaration_style = style;
+        self
+    }
+
+    /// Prefix every object member with `readonly`.
+    pub fn readonly(mut self, readonly: bool) -> Self {
+        self.readonly = readonly;
+        self
+    }
+
+    /// Emit `.d.ts` style `export declare ...` declarations.
+    pub fn declare(mut self, declare: bool) -> Self {
+        self.declare = declare;
+        self
+    }
+
+    /// Terminate statements and members with `;` (default `true`).
+    ///
+    /// When disabled, single-line object members are separated by `,` and
+    /// pretty-printed members by newlines only.
+    pub fn semicolons(mut self, semicolons: bool) -> Self {
+        self.semicolons = semicolons;
+        self
+    }
+
+    pub fn quote_style(mut self, style: QuoteStyle) -> Self {
+        self.quote_style = style;
+        self
+    }
+
+    /// Default rendering of `Option<T>` fields; a field's own
+    /// `#[schema(optional_style = "...")]` takes precedence.
+    pub fn optional_style(mut self, style: OptionalStyle) -> Self {
+        self.optional_style = style;
+        self
+    }
+
+    pub fn any_style(mut self, style: AnyStyle) -> Self {
+        self.any_style = style;
+        self
+    }
+
+    /// Replace the default `// This file is auto-generated ...` banner.
+    ///
+    /// The text is emitted verbatim, so it must already be a comment.
+    pub fn banner(mut self, banner: impl Into<String>) -> Self {
+        self.banner = Some(banner.into());
+        self
+    }
+
+    /// Omit the banner entirely.
+    pub fn no_banner(mut self) -> Self {
+        self.banner = None;
+        self
+    }
+
+    /// Add a verbatim header line emitted after the banner, e.g.
+    /// `/* eslint-disable */`.
+    pub fn header(mut self, line: impl Into<String>) -> Self {
+        self.headers.push(line.into());
+        self
+    }
+
+    fn terminator(&self) -> &'static str {
+        if self.semicolons {
+            ";"
+        } else {
+            ""
+        }
+    }
+
+    fn export_prefix(&self) -> &'static str {
+        if self.declare {
+            "export declare"
+        } else {
+            "export"
+        }
+    }
+}
+
+/// What kind of top-level statement a [`Declaration`] renders as.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DeclarationKind {
+    /// `export type Name = ...` (or `interface`, see [`DeclarationStyle`])
+    #[default]
+    Type,
+    /// `export enum Name { Member = 'value', ... }` for a `Schema::Enum` or
+    /// `Schema::NumericEnum`.
+    ///
+    /// Holds the member names, parallel to the enum values.
+    Enum(Vec<String>),
+    /// `export const NAME = ['value', ...] as const` listing the values of a
+    /// `Schema::Enum` or `Schema::NumericEnum`; `name` is the constant's name.
+    ConstValues,
+}
+
+/// A named top-level type to emit in a generated TypeScript file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub name: String,
+    pub schema: Schema,
+    pub kind: DeclarationKind,
+}
+
+impl Declaration {
+    pub fn new(name: impl Into<String>, schema: Schema) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            kind: DeclarationKind::Type,
+        }
+    }
+
+    /// A TypeScript `enum` whose members are named by `members`.
+    pub fn enumeration(name: impl Into<String>, schema: Schema, members: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            kind: DeclarationKind::Enum(members),
+        }
+    }
+
+    /// A runtime `as const` array of the values of `type_name`, named
+    /// `TYPE_NAME_VALUES` (e.g. `Status` -> `STATUS_VALUES`).
+    pub fn const_values(type_name: &str, schema: Schema) -> Self {
+        Self {
+            name: format!("{}_VALUES", screaming_snake(type_name)),
+            schema,
+            kind: DeclarationKind::ConstValues,
+        }
+    }
+}
+
+/// Render a schema as an inline TypeScript type expression.
+pub fn render_ts(schema: &Schema, config: &TsConfig) -> String {
+    Renderer { config }.render(schema, 0)
+}
+
+/// Render a single top-level declaration, without trailing newline.
+pub fn render_declaration(declaration: &Declaration, config: &TsConfig) -> String {
+    let renderer = Renderer { config };
+    let prefix = config.export_prefix();
+    let name = &declaration.name;
+    let terminator = config.terminator();
+
+    let literals = renderer.enum_literals(&declaration.schema);
+
+    match (&declaration.kind, literals) {
+        (DeclarationKind::Enum(members), Some(values)) => {
+            let members: Vec<String> = members
+                .iter()
+                .zip(values)
+                .map(|(member, value)| format!("{} = {value}", renderer.property_name(member)))
+                .collect();
+            format!("{prefix} enum {name} {}", renderer.block(&members, ",", 0))
+        }
+        (DeclarationKind::ConstValues, Some(values)) => {
+            let values = values.join(", ");
+            if config.declare {
+                format!("{prefix} const {name}: readonly [{values}]{terminator}")
+            } else {
+                format!("{prefix} const {name} = [{values}] as const{terminator}")
+            }
+        }
+        _ => match declaration.schema.resolved() {
+            Schema::Object(fields) if config.declaration_style == DeclarationStyle::Interface => {
+                format!("{prefix} interface {name} {}", renderer.object(fields, 0))
+            }
+            schema => format!(
+                "{prefix} type {name} = {}{terminator}",
+                renderer.render(schema, 0)
+            ),
+        },
+    }
+}
+
+/// Generate the full content of a TypeScript file.
+pub fn generate_ts_file_with(declarations: Vec<Declaration>, config: &TsConfig) -> String {
+    let mut content = String::new();
+
+    let mut preamble: Vec<&str> = Vec::new();
+    if let Some(banner) = &config.banner {
+        preamble.push(banner);
+    }
+    preamble.extend(config.headers.iter().map(String::as_str));
+    if !preamble.is_empty() {
+        content.push_str(&preamble.join("\n"));
+        content.push_str("\n\n");
+    }
+
+    let needs_json_value = config.any_style == AnyStyle::JsonValue
+        && declarations.iter().any(|d| contains_any(&d.schema))
+        && !declarations.iter().any(|d| d.name == JSON_VALUE);
+    if needs_json_value {
+        content.push_str(&format!(
+            "{} type {JSON_VALUE} = string | number | boolean | null | {JSON_VALUE}[] | {{ [key: string]: {JSON_VALUE} }}{}\n\n",
+            config.export_prefix(),
+            config.terminator()
+        ));
+    }
+
+    for declaration in &declarations {
+        content.push_str(&render_declaration(declaration, config));
+        content.push_str("\n\n");
+    }
+
+    content
+}
+
+/// Whether rendering `schema` would mention `Schema::Any`.
+fn contains_any(schema: &Schema) -> bool {
+    match schema {
+        Schema::Any => true,
+        Schema::Array(item) => contains_any(item),
+        Schema::Object(fields) => fields.iter().any(|f| contains_any(&f.schema)),
+        Schema::Union(members) | Schema::Tuple(members) => members.iter().any(contains_any),
+        Schema::Record { key, value, .. } => contains_any(key) || contains_any(value),
+        Schema::Branded { schema, .. }
+        | Schema::Constrained { schema, .. }
+        | Schema::Ordered(schema) => contains_any(schema),
+        Schema::Shared(schema) => contains_any(schema),
+        // `Custom` is rendered verbatim
+        _ => false,
+    }
+}
+
+/// Export declarations to a TypeScript file using the given configuration.
+pub fn export_to_file_with(
+    declarations: Vec<Declaration>,
+    path: &str,
+    config: &TsConfig,
+) -> std::io::Result<()> {
+    let content = generate_ts_file_with(declarations, config);
+    std::fs::write(path, content)
+}
+
+struct Renderer<'a> {
+    config: &'a TsConfig,
+}
+
+impl Renderer<'_> {
+    fn render(&self, schema: &Schema, depth: usize) -> String {
+        match schema {
+            Schema::String => "string".to_string(),
+            Schema::Number | Schema::Integer => "number".to_string(),
+            Schema::Boolean => "boolean".to_string(),
+            Schema::Null => "null".to_string(),
+            Schema::Any => match self.config.any_style {
+                AnyStyle::Unknown => "unknown",
+                AnyStyle::JsonValue => JSON_VALUE,
+                AnyStyle::Any => "any",
+            }
+            .to_string(),
+            Schema::Array(item) => {
+                let inner = self.render(item, depth);
+                if needs_parens(item) || is_intersection(item) {
+                    format!("({inner})[]")
+                } else {
+                    format!("{inner}[]")
+                }
+            }
+            Schema::Object(fields) => self.object(fields, depth),
+            Schema::Enum(_) | Schema::NumericEnum(_) => {
+                self.union(self.enum_literals(schema).unwrap_or_default())
+            }
+            Schema::Union(members) => {
+                self.union(members.iter().map(|m| self.render(m, depth)).collect())
+            }
+            Schema::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|i| self.render(i, depth)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Schema::Ref(name) => name.clone(),
+            Schema::Constrained { schema, .. } | Schema::Ordered(schema) => {
+                self.render(schema, depth)
+            }
+            Schema::Shared(schema) => self.render(schema, depth),
+            Schema::Custom { ts, .. } => ts.clone(),
+            Schema::Bytes(BytesEncoding::Array) => "Uint8Array".to_string(),
+            Schema::Bytes(_) => "string".to_string(),
+            Schema::Branded { name, schema } => {
+                let inner = self.render(schema, depth);
+                let inner = if needs_parens(schema) {
+                    format!("({inner})")
+                } else {
+                    inner
+                };
+                format!(
+                    "{inner} & {{ readonly __brand: {} }}",
+                    self.config.quote_style.quote(name)
+                )
+            }
+            Schema::Record { key, value, .. } => {
+                let record = format!(
+                    "Record<{}, {}>",
+                    self.render(key, depth),
+                    self.render(value, depth)
+                );
+                // A map holds some of the enum's keys, not necessarily all
+                if is_literal_key(key) {
+                    format!("Partial<{record}>")
+                } else {
+                    record
+                }
+            }
+        }
+    }
+
+    /// Literal types for the values of an enum schema.
+    fn enum_literals(&self, schema: &Schema) -> Option<Vec<String>> {
+        match schema.resolved() {
+            Schema::Enum(values) => Some(
+                values
+                    .iter()
+                    .map(|v| self.config.quote_style.quote(v))
+                    .collect(),
+            ),
+            Schema::NumericEnum(values) => Some(values.iter().map(i64::to_string).collect()),
+            // e.g. a `ts_enum` type, whose inline form is the enum's name
+            Schema::Custom { schema, .. } => self.enum_literals(schema),
+            _ => None,
+        }
+    }
+
+    fn union(&self, members: Vec<String>) -> String {
+        if members.is_empty() {
+            "never".to_string()
+        } else {
+            members.join(" | ")
+        }
+    }
+
+    fn object(&self, fields: &[Field], depth: usize) -> String {
+        let members: Vec<String> = fields.iter().map(|f| self.member(f, depth + 1)).collect();
+        self.block(&members, ";", depth)
+    }
+
+    /// Lay out `{ ... }` members, single-line or one per line.
+    ///
+    /// A `;` delimiter follows [`TsConfig::semicolons`]: when disabled, pretty
+    /// members end with a newline only and single-line members are separated
+    /// by `,`. Other delimiters (`,` for enums) terminate pretty members and
+    /// separate single-line ones.
+    fn block(&self, members: &[String], delimiter: &str, depth: usize) -> String {
+        if members.is_empty() {
+            return "{}".to_string();
+        }
+
+        let semicolon = delimiter == ";";
+        if self.config.pretty {
+            let pad = " ".repeat(self.config.indent * (depth + 1));
+            let close = " ".repeat(self.config.indent * depth);
+            let terminator = if semicolon {
+                self.config.terminator()
+            } else {
+                delimiter
+            };
+            let body: Vec<String> = members
+                .iter()
+                .map(|m| format!("{pad}{m}{terminator}"))
+                .collect();
+            format!("{{\n{}\n{close}}}", body.join("\n"))
+        } else if semicolon && self.config.semicolons {
+            let body: Vec<String> = members.iter().map(|m| format!("{m};")).collect();
+            format!("{{ {} }}", body.join(" "))
+        } else {
+            format!("{{ {} }}", members.join(", "))
+        }
+    }
+
+    fn member(&self, field: &Field, depth: usize) -> String {
+        let readonly = if self.config.readonly {
+            "readonly "
+        } else {
+            ""
+        };
+        let name = self.property_name(&field.name);
+        let ty = self.render(&field.schema, depth);
+
+        let (optional, nullable) = match (field.required, field.nullable) {
+            (required, false) => (!required, false),
+            (true, true) => (false, true),
+            (false, true) => match field.optional_style.unwrap_or(self.config.optional_style) {
+                OptionalStyle::Optional => (true, false),
+                OptionalStyle::OptionalNullable => (true, true),
+                OptionalStyle::Nullable => (false, true),
+            },
+        };
+        let marker = if optional { "?" } else { "" };
+        let null = if nullable { " | null" } else { "" };
+        format!("{readonly}{name}{marker}: {ty}{null}")
+    }
+
+    fn property_name(&self, name: &str) -> String {
+        if is_identifier(name) {
+            name.to_string()
+        } else {
+            self.config.quote_style.quote(name)
+        }
+    }
+}
+
+fn screaming_snake(name: &str) -> String {
+    let mut result = String::new();
+    let mut prev_lower = false;
+    for ch in name.chars() {
+        if ch.is_uppercase() && prev_lower {
+            result.push('_');
+        }
+        prev_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        result.extend(ch.to_uppercase());
+    }
+    result
+}
+
+fn is_literal_key(schema: &Schema) -> bool {
+    match schema.resolved() {
+        Schema::Enum(_) | Schema::NumericEnum(_) => true,
+        Schema::Constrained { schema, .. }
+        | Schema::Branded { schema, .. }
+        | Schema::Custom { schema, .. } => is_literal_key(schema),
+        _ => false,
+    }
+}
+
+fn is_intersection(schema: &Schema) -> bool {
+    match schema.resolved() {
+        Schema::Branded { .. } => true,
+        Schema::Constrained { schema, .. } => is_intersection(schema),
+        _ => false,
+    }
+}
+
+fn needs_parens(schema: &Schema) -> bool {
+    match schema.resolved() {
+        Schema::Constrained { schema, .. } => needs_parens(schema),
+        Schema::Union(members) => members.len() > 1,
+        Schema::Enum(variants) => variants.len() > 1,
+        Schema::NumericEnum(values) => values.len() > 1,
+        // Hand-written types may be unions, function types, `keyof X` and so
+        // on; only a plain (possibly qualified) type name is safe bare
+        Schema::Custom { ts, .. } => !ts.split('.').all(is_identifier),
+        _ => false,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == '$' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> Schema {
+        Schema::Object(vec![
+            Field::new("name", Schema::String),
+            Field::new("tags", Schema::Array(Box::new(Schema::String))),
+        ])
+    }
+
+    #[test]
+    fn default_config_matches_classic_output() {
+        let out = generate_ts_file_with(vec![Declaration::new("User", user())], &TsConfig::new());
+        assert_eq!(
+            out,
+            "// This file is auto-generated by schema-bridge\n\n\
+             export type User = { name: string; tags: string[]; };\n\n"
+        );
+    }
+
+    #[test]
+    fn pretty_interface_readonly() {
+        let config = TsConfig::new()
+            .pretty(true)
+            .indent(4)
+            .readonly(true)
+            .declaration_style(DeclarationStyle::Interface);
+        let out = render_declaration(&Declaration::new("User", user()), &config);
+        assert_eq!(
+            out,
+            "export interface User {\n    readonly name: string;\n    readonly tags: string[];\n}"
+        );
+    }
+
+    #[test]
+    fn interface_falls_back_to_alias_for_non_objects() {
+        let config = TsConfig::new().declaration_style(DeclarationStyle::Interface);
+        let decl = Declaration::new("Status", Schema::Enum(vec!["A".into(), "B".into()]));
+        assert_eq!(
+            render_declaration(&decl, &config),
+            "export type Status = 'A' | 'B';"
+        );
+    }
+
+    #[test]
+    fn nested_objects_are_indented() {
+        let schema = Schema::Object(vec![Field::new(
+            "inner",
+            Schema::Object(vec![Field::new("x", Schema::Number)]),
+        )]);
+        let config = TsConfig::new().pretty(true);
+        assert_eq!(
+            render_ts(&schema, &config),
+            "{\n  inner: {\n    x: number;\n  };\n}"
+        );
+    }
+
+    #[test]
+    fn declare_double_quotes_no_semicolons() {
+        let config = TsConfig::new()
+            .declare(true)
+            .semicolons(false)
+            .quote_style(QuoteStyle::Double);
+        let decl = Declaration::new(
+            "Thing",
+            Schema::Object(vec![
+                Field::new("kind", Schema::Enum(vec!["a\"b".into()])),
+                Field::new("n", Schema::Integer),
+            ]),
+        );
+        assert_eq!(
+            render_declaration(&decl, &config),
+            r#"export declare type Thing = { kind: "a\"b", n: number }"#
+        );
+    }
+
+    #[test]
+    fn banner_and_headers() {
+        let config = TsConfig::new()
+            .banner("/* generated */")
+            .header("/* eslint-disable */")
+            .header("// @ts-nocheck");
+        let out = generate_ts_file_with(vec![], &config);
+        assert_eq!(
+            out,
+            "/* generated */\n/* eslint-disable */\n// @ts-nocheck\n\n"
+        );
+        assert_eq!(
+            generate_ts_file_with(vec![], &TsConfig::new().no_banner()),
+            ""
+        );
+    }
+
+    #[test]
+    fn optional_style_policies() {
+        let mut email = Field::optional("email", Schema::String);
+        let schema = Schema::Object(vec![
+            email.clone(),
+            Field::new("nickname", Schema::String).required(false),
+        ]);
+
+        let render = |style| render_ts(&schema, &TsConfig::new().optional_style(style));
+        assert_eq!(
+            render(OptionalStyle::Nullable),
+            "{ email: string | null; nickname?: string; }"
+        );
+        assert_eq!(
+            render(OptionalStyle::OptionalNullable),
+            "{ email?: string | null; nickname?: string; }"
+        );
+        assert_eq!(
+            render(OptionalStyle::Optional),
+            "{ email?: string; nickname?: string; }"
+        );
+
+        email.optional_style = Some(OptionalStyle::Optional);
+        let schema = Schema::Object(vec![email]);
+        assert_eq!(render_ts(&schema, &TsConfig::new()), "{ email?: string; }");
+    }
+
+    #[test]
+    fn enum_declaration() {
+        let decl = Declaration::enumeration(
+            "Status",
+            Schema::Enum(vec!["active".into(), "on-hold".into()]),
+            vec!["Active".into(), "OnHold".into()],
+        );
+        assert_eq!(
+            render_declaration(&decl, &TsConfig::new()),
+            "export enum Status { Active = 'active', OnHold = 'on-hold' }"
+        );
+        assert_eq!(
+            render_declaration(&decl, &TsConfig::new().pretty(true)),
+            "export enum Status {\n  Active = 'active',\n  OnHold = 'on-hold',\n}"
+        );
+    }
+
+    #[test]
+    fn const_values_declaration() {
+        let schema = Schema::Enum(vec!["Active".into(), "Inactive".into()]);
+        let decl = Declaration::const_values("OrderStatus", schema);
+        assert_eq!(decl.name, "ORDER_STATUS_VALUES");
+        assert_eq!(
+            render_declaration(&decl, &TsConfig::new()),
+            "export const ORDER_STATUS_VALUES = ['Active', 'Inactive'] as const;"
+        );
+        assert_eq!(
+            render_declaration(&decl, &TsConfig::new().declare(true)),
+            "export declare const ORDER_STATUS_VALUES: readonly ['Active', 'Inactive'];"
+        );
+    }
+
+    #[test]
+    fn numeric_enum_declarations() {
+        let schema = Schema::NumericEnum(vec![1, 2, -1]);
+        assert_eq!(render_ts(&schema, &TsConfig::new()), "1 | 2 | -1");
+
+        let decl = Declaration::enumeration(
+            "Code",
+            schema.clone(),
+            vec!["Ok".into(), "Retry".into(), "Fatal".into()],
+        );
+        assert_eq!(
+            render_declaration(&decl, &TsConfig::new()),
+            "export enum Code { Ok = 1, Retry = 2, Fatal = -1 }"
+        );
+        assert_eq!(
+            render_declaration(&Declaration::const_values("Code", schema), &TsConfig::new()),
+            "export const CODE_VALUES = [1, 2, -1] as const;"
+        );
+    }
+
+    #[test]
+    fn branded_types() {
+        let user_id = Schema::Branded {
+            name: "UserId".into(),
+            schema: Box::new(Schema::String),
+        };
+        assert_eq!(
+            render_ts(&user_id, &TsConfig::new()),
+            "string & { readonly __brand: 'UserId' }"
+        );
+        assert_eq!(
+            render_ts(&Schema::Array(Box::new(user_id)), &TsConfig::new()),
+            "(string & { readonly __brand: 'UserId' })[]"
+        );
+
+        let code = Schema::Branded {
+            name: "Code".into(),
+            schema: Box::new(Schema::NumericEnum(vec![1, 2])),
+        };
+        assert_eq!(
+            render_ts(&code, &TsConfig::new()),
+            "(1 | 2) & { readonly __brand: 'Code' }"
+        );
+    }
+
+    #[test]
+    fn any_styles() {
+        let any = Schema::Any;
+        assert_eq!(render_ts(&any, &TsConfig::new()), "any");
+        assert_eq!(
+            render_ts(&any, &TsConfig::new().any_style(AnyStyle::Unknown)),
+            "unknown"
+        );
+        assert_eq!(
+            render_ts(&any, &TsConfig::new().any_style(AnyStyle::JsonValue)),
+            "JsonValue"
+        );
+    }
+
+    #[test]
+    fn json_value_alias_is_declared_once_when_used() {
+        let config = TsConfig::new().no_banner().any_style(AnyStyle::JsonValue);
+        let event = Declaration::new(
+            "Event",
+            Schema::Object(vec![Field::new("payload", Schema::Any)]),
+        );
+        let plain = Declaration::new("Name", Schema::String);
+
+        assert_eq!(
+            generate_ts_file_with(vec![event.clone(), event], &config),
+            "export type JsonValue = string | number | boolean | null | JsonValue[] | { [key: string]: JsonValue };\n\n\
+             export type Event = { payload: JsonValue; };\n\n\
+             export type Event = { payload: JsonValue; };\n\n"
+        );
+        assert_eq!(
+            generate_ts_file_with(vec![plain.clone()], &config),
+            "export type Name = string;\n\n"
+        );
+        assert_eq!(
+            generate_ts_file_with(vec![plain], &TsConfig::new().no_banner()),
+            "export type Name = string;\n\n"
+        );
+    }
+
+    #[test]
+    fn custom_types_render_verbatim() {
+        let decimal = Schema::Custom {
+            ts: "Decimal".into(),
+            schema: Box::new(Schema::String),
+        };
+        assert_eq!(render_ts(&decimal, &TsConfig::new()), "Decimal");
+
+        let callback = Schema::Custom {
+            ts: "() => void".into(),
+            schema: Box::new(Schema::Any),
+        };
+        assert_eq!(
+            render_ts(&Schema::Array(Box::new(callback)), &TsConfig::new()),
+            "(() => void)[]"
+        );
+
+        let array_of = |ts: &str| {
+            let custom = Schema::Custom {
+                ts: ts.into(),
+                schema: Box::new(Schema::Any),
+            };
+            render_ts(&Schema::Array(Box::new(custom)), &TsConfig::new())
+        };
+        assert_eq!(array_of("Decimal"), "Decimal[]");
+        assert_eq!(array_of("money.Amount"), "money.Amount[]");
+        assert_eq!(array_of("keyof Theme"), "(keyof Theme)[]");
+        assert_eq!(array_of("Map<string, number>"), "(Map<string, number>)[]");
+    }
+
+    #[test]
+    fn bytes_render_by_encoding() {
+        let render = |encoding| render_ts(&Schema::Bytes(encoding), &TsConfig::new());
+        assert_eq!(render(BytesEncoding::Base64), "string");
+        assert_eq!(render(BytesEncoding::Hex), "string");
+        assert_eq!(render(BytesEncoding::Array), "Uint8Array");
+    }
+
+    #[test]
+    fn union_array_is_parenthesized() {
+        let schema = Schema::Array(Box::new(Schema::Union(vec![Schema::String, Schema::Null])));
+        assert_eq!(render_ts(&schema, &TsConfig::new()), "(string | null)[]");
+    }
+
+    #[test]
+    fn non_identifier_property_names_are_quoted() {
+        let schema = Schema::Object(vec![Field::new("content-type", Schema::String)]);
+        assert_eq!(
+            render_ts(&schema, &TsConfig::new()),
+            "{ 'content-type': string; }"
+        );
+    }
+}
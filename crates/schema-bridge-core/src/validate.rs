@@ -0,0 +1,1235 @@
+//! Validation of JSON values against a [`Schema`].
+//!
+//! [`CompiledSchema`] does the per-schema work once: `Ref`s are resolved to
+//! node indices, `Constrained` wrappers are folded into the nodes they apply
+//! to, patterns and formats are compiled to regexes and object fields get a
+//! name lookup table. Validating a value is then a single walk over the value
+//! that allocates only when it finds an error.
+
+use crate::{cache, BytesEncoding, Constraints, Declaration, Schema, SchemaBridge};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hasher};
+
+mod ser;
+
+/// Check a Rust value against `T::to_schema()`, including all constraints,
+/// without building a `serde_json::Value`.
+///
+/// The schema is compiled once per type. Only values meeting a union with
+/// several members of the same JSON kind are converted to a `Value` first.
+///
+/// # Panics
+///
+/// If the schema of `T` does not compile, e.g. has an invalid `pattern`.
+pub fn validate_value<T: Serialize + SchemaBridge + ?Sized + 'static>(
+    value: &T,
+) -> Result<(), Vec<ValidationError>> {
+    compiled::<T>(false).validate_value(value)
+}
+
+/// Parse `json`, check it against `T::to_schema_input()` including all
+/// constraints, and deserialize it, as [`from_value_validated`] does.
+///
+/// # Panics
+///
+/// If the schema of `T` does not compile, e.g. has an invalid `pattern`.
+pub fn from_str_validated<T: DeserializeOwned + SchemaBridge + 'static>(
+    json: &str,
+) -> Result<T, DeserializeError> {
+    let value = serde_json::from_str(json).map_err(DeserializeError::Syntax)?;
+    from_value_validated(value)
+}
+
+/// Check `value` against `T::to_schema_input()` including all constraints,
+/// and deserialize it. Failures from both passes are reported together:
+/// the schema's, then serde's for paths the schema did not already flag.
+///
+/// # Panics
+///
+/// If the schema of `T` does not compile, e.g. has an invalid `pattern`.
+pub fn from_value_validated<T: DeserializeOwned + SchemaBridge + 'static>(
+    value: Value,
+) -> Result<T, DeserializeError> {
+    let checked = compiled::<T>(true).validate(&value);
+    // Values the schema cannot describe, e.g. a `u8` above 255, fail here
+    let parsed = serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        ValidationError {
+            path: if path == "." { String::new() } else { path },
+            message: e.into_inner().to_string(),
+        }
+    });
+    match (checked, parsed) {
+        (Ok(()), Ok(value)) => Ok(value),
+        (Ok(()), Err(error)) => Err(DeserializeError::Invalid(vec![error])),
+        (Err(errors), Ok(_)) => Err(DeserializeError::Invalid(errors)),
+        (Err(mut errors), Err(error)) => {
+            if !errors.iter().any(|e| covers(&e.path, &error.path)) {
+                errors.push(error);
+            }
+            Err(DeserializeError::Invalid(errors))
+        }
+    }
+}
+
+/// Whether an error at `parent` already accounts for one at `path`.
+fn covers(parent: &str, path: &str) -> bool {
+    parent.is_empty()
+        || path
+            .strip_prefix(parent)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+/// The compiled output (or input) schema of `T`, built once per type.
+fn compiled<T: SchemaBridge + ?Sized + 'static>(input: bool) -> &'static CompiledSchema {
+    cache::compiled::<T>(input, || {
+        let schema = if input {
+            T::schema_input_ref()
+        } else {
+            T::schema_ref()
+        };
+        CompiledSchema::compile(schema).unwrap_or_else(|e| {
+            panic!(
+                "schema of `{}` does not compile: {e}",
+                std::any::type_name::<T>()
+            )
+        })
+    })
+}
+
+/// A value that does not match its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Where the value is, e.g. `user.tags[2]`; empty for the root.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A schema that cannot be compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    /// A `pattern` constraint that is not a valid regular expression.
+    InvalidPattern { pattern: String, message: String },
+    /// A `Schema::Ref` with no matching definition.
+    UnresolvedRef(String),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileError::InvalidPattern { pattern, message } => {
+                write!(f, "invalid pattern `{pattern}`: {message}")
+            }
+            CompileError::UnresolvedRef(name) => write!(f, "unresolved reference `{name}`"),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// Why [`from_str_validated`] or [`from_value_validated`] rejected its input.
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// The input is not JSON.
+    Syntax(serde_json::Error),
+    /// The JSON breaks the schema's constraints, or serde could not
+    /// deserialize it; each failure keyed by path.
+    Invalid(Vec<ValidationError>),
+}
+
+impl DeserializeError {
+    /// The failures keyed by path; empty for a syntax error.
+    pub fn errors(&self) -> &[ValidationError] {
+        match self {
+            DeserializeError::Syntax(_) => &[],
+            DeserializeError::Invalid(errors) => errors,
+        }
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::Syntax(e) => write!(f, "invalid JSON: {e}"),
+            DeserializeError::Invalid(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    error.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeserializeError::Syntax(e) => Some(e),
+            DeserializeError::Invalid(_) => None,
+        }
+    }
+}
+
+/// A [`Schema`] pre-processed into a reusable validator.
+///
+/// Compile once, e.g. into a `static`, and validate many values:
+///
+/// ```
+/// use schema_bridge_core::{CompiledSchema, Constraints, Field, Schema};
+///
+/// let age = Field::new("age", Schema::Integer)
+///     .constraints(Constraints::default().min(0.0).max(150.0));
+/// let validator = CompiledSchema::compile(&Schema::Object(vec![age])).unwrap();
+///
+/// assert!(validator.validate(&serde_json::json!({ "age": 30 })).is_ok());
+/// let errors = validator.validate(&serde_json::json!({ "age": 200 })).unwrap_err();
+/// assert_eq!(errors[0].to_string(), "age: must be at most 150");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledSchema {
+    nodes: Vec<Node>,
+    root: NodeId,
+    /// An `Any` node, for walking values nothing is checked against
+    any: NodeId,
+    /// Names of `Branded` nodes, for messages like `expected UserId (string)`
+    names: HashMap<NodeId, String>,
+}
+
+type NodeId = usize;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Any,
+    Null,
+    Boolean,
+    Number(Number),
+    String(Text),
+    Array {
+        item: NodeId,
+        len: Length,
+    },
+    Tuple(Vec<NodeId>),
+    Object(Object),
+    Record {
+        key: NodeId,
+        value: NodeId,
+        len: Length,
+    },
+    Union(Vec<NodeId>),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Number {
+    integer: bool,
+    min: Option<f64>,
+    max: Option<f64>,
+    /// Allowed values of a `NumericEnum`
+    values: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Text {
+    len: Length,
+    pattern: Option<Pattern>,
+    format: Option<(String, Format)>,
+    one_of: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Length {
+    min: Option<usize>,
+    max: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Format {
+    Pattern(Pattern),
+    Ipv4,
+    Ipv6,
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    source: String,
+    matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// `^[class]{min,max}$` over ASCII characters, the shape of most
+    /// identifier and key patterns, checked without the regex engine
+    Class {
+        ascii: u128,
+        min: usize,
+        max: Option<usize>,
+    },
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+struct Object {
+    fields: Vec<ObjectField>,
+    index: HashMap<String, usize, BuildHasherDefault<FieldHasher>>,
+    required: usize,
+}
+
+#[derive(Debug, Clone)]
+struct ObjectField {
+    name: String,
+    node: NodeId,
+    required: bool,
+    nullable: bool,
+}
+
+impl CompiledSchema {
+    /// Compile `schema`, which must not contain `Ref`s.
+    pub fn compile(schema: &Schema) -> Result<CompiledSchema, CompileError> {
+        Self::compile_with(schema, &[])
+    }
+
+    /// Compile `schema`, resolving `Ref`s against `definitions` by name, e.g.
+    /// those collected from [`SchemaBridge::ts_declarations`]. Definitions
+    /// may refer to each other and to themselves.
+    ///
+    /// [`SchemaBridge::ts_declarations`]: crate::SchemaBridge::ts_declarations
+    pub fn compile_with(
+        schema: &Schema,
+        definitions: &[Declaration],
+    ) -> Result<CompiledSchema, CompileError> {
+        let mut compiler = Compiler {
+            nodes: Vec::new(),
+            definitions: definitions
+                .iter()
+                .map(|declaration| (declaration.name.as_str(), &declaration.schema))
+                .collect(),
+            resolved: HashMap::new(),
+            shared: HashMap::new(),
+            patterns: HashMap::new(),
+            names: HashMap::new(),
+        };
+        let any = compiler.push(Node::Any);
+        let root = compiler.compile(schema, Constraints::default())?;
+        Ok(CompiledSchema {
+            nodes: compiler.nodes,
+            root,
+            any,
+            names: compiler.names,
+        })
+    }
+
+    /// Check `value`, returning every mismatch found.
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        self.check(self.root, value, &Path::Root, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check a Rust value by serializing it into the validator, as
+    /// [`validate_value`] does. Unlike that function, works for types that
+    /// borrow.
+    pub fn validate_value<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+    ) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        match value.serialize(ser::Validator::new(
+            self,
+            self.root,
+            &Path::Root,
+            &mut errors,
+        )) {
+            Ok(()) => {}
+            Err(ser::Interrupt::Ambiguous) => {
+                return match serde_json::to_value(value) {
+                    Ok(value) => self.validate(&value),
+                    Err(e) => Err(vec![Path::Root.error(e.to_string())]),
+                }
+            }
+            Err(ser::Interrupt::Failed(error)) => errors.push(error),
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn check(&self, id: NodeId, value: &Value, path: &Path, errors: &mut Vec<ValidationError>) {
+        match (&self.nodes[id], value) {
+            (Node::Any, _) | (Node::Null, Value::Null) | (Node::Boolean, Value::Bool(_)) => {}
+            (Node::Number(number), Value::Number(n)) => number.check(n, path, errors),
+            (Node::String(text), Value::String(s)) => text.check(s, path, errors),
+            (Node::Array { item, len }, Value::Array(items)) => {
+                len.check(items.len(), "items", path, errors);
+                for (i, value) in items.iter().enumerate() {
+                    self.check(*item, value, &Path::Index(path, i), errors);
+                }
+            }
+            (Node::Tuple(ids), Value::Array(items)) => {
+                if ids.len() != items.len() {
+                    errors.push(path.error(format!(
+                        "expected {} items, found {}",
+                        ids.len(),
+                        items.len()
+                    )));
+                    return;
+                }
+                for (i, (id, value)) in ids.iter().zip(items).enumerate() {
+                    self.check(*id, value, &Path::Index(path, i), errors);
+                }
+            }
+            (Node::Object(object), Value::Object(map)) => {
+                self.check_object(object, map, path, errors)
+            }
+            (Node::Record { key, value, len }, Value::Object(map)) => {
+                len.check(map.len(), "entries", path, errors);
+                for (k, v) in map {
+                    let path = Path::Key(path, k);
+                    self.check_key(*key, k, &path, errors);
+                    self.check(*value, v, &path, errors);
+                }
+            }
+            (Node::Union(members), _) => self.check_union(members, value, path, errors),
+            (_, value) => errors.push(path.error(self.mismatch(id, kind_of(value)))),
+        }
+    }
+
+    fn check_object(
+        &self,
+        object: &Object,
+        map: &Map<String, Value>,
+        path: &Path,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        // Unknown keys pass, as serde ignores them by default
+        let mut required = 0;
+        for (name, value) in map {
+            let Some(field) = object.index.get(name).map(|&i| &object.fields[i]) else {
+                continue;
+            };
+            required += usize::from(field.required);
+            if !(field.nullable && value.is_null()) {
+                self.check(field.node, value, &Path::Key(path, name), errors);
+            }
+        }
+        if required < object.required {
+            for field in &object.fields {
+                if field.required && !map.contains_key(&field.name) {
+                    errors.push(Path::Key(path, &field.name).error("is required"));
+                }
+            }
+        }
+    }
+
+    /// Map keys are strings: check them against the key schema's text rules.
+    fn check_key(&self, id: NodeId, key: &str, path: &Path, errors: &mut Vec<ValidationError>) {
+        match &self.nodes[id] {
+            Node::String(text) => {
+                let start = errors.len();
+                text.check(key, path, errors);
+                for error in &mut errors[start..] {
+                    error.message = format!("invalid key: {}", error.message);
+                }
+            }
+            Node::Union(members) => {
+                let mut scratch = Vec::new();
+                let matches = members.iter().any(|&member| {
+                    scratch.clear();
+                    self.check_key(member, key, path, &mut scratch);
+                    scratch.is_empty()
+                });
+                if !matches {
+                    errors.push(path.error("invalid key"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Narrow the members down to those that could hold a value of this kind
+    /// (and, for objects, with all their required fields), so a mismatch
+    /// reports the errors of the one variant the value was meant to be.
+    fn check_union(
+        &self,
+        members: &[NodeId],
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let mut candidates = members.iter().filter(|&&id| self.admits(id, value));
+        match (candidates.next(), candidates.next()) {
+            (None, _) => {
+                let kinds: Vec<String> = members.iter().map(|&id| self.expected(id)).collect();
+                errors.push(path.error(format!(
+                    "expected {}, found {}",
+                    kinds.join(" | "),
+                    kind_of(value)
+                )));
+            }
+            (Some(&only), None) => self.check(only, value, path, errors),
+            (Some(_), Some(_)) => {
+                let mut scratch = Vec::new();
+                let matches = members.iter().any(|&member| {
+                    scratch.clear();
+                    self.check(member, value, path, &mut scratch);
+                    scratch.is_empty()
+                });
+                if !matches {
+                    errors.push(path.error("does not match any variant"));
+                }
+            }
+        }
+    }
+
+    fn admits(&self, id: NodeId, value: &Value) -> bool {
+        match (&self.nodes[id], value) {
+            (Node::Any, _)
+            | (Node::Null, Value::Null)
+            | (Node::Boolean, Value::Bool(_))
+            | (Node::Number(_), Value::Number(_))
+            | (Node::String(_), Value::String(_))
+            | (Node::Array { .. } | Node::Tuple(_), Value::Array(_))
+            | (Node::Record { .. }, Value::Object(_)) => true,
+            (Node::Object(object), Value::Object(map)) => object
+                .fields
+                .iter()
+                .all(|field| !field.required || map.contains_key(&field.name)),
+            (Node::Union(members), _) => members.iter().any(|&id| self.admits(id, value)),
+            _ => false,
+        }
+    }
+}
+
+impl CompiledSchema {
+    fn mismatch(&self, id: NodeId, found: &str) -> String {
+        format!("expected {}, found {found}", self.expected(id))
+    }
+
+    fn expected(&self, id: NodeId) -> String {
+        let kind = match &self.nodes[id] {
+            Node::Union(members) => {
+                let kinds: Vec<String> = members.iter().map(|&id| self.expected(id)).collect();
+                kinds.join(" | ")
+            }
+            node => node.kind().to_string(),
+        };
+        match self.names.get(&id) {
+            Some(name) => format!("{name} ({kind})"),
+            None => kind,
+        }
+    }
+}
+
+impl Node {
+    fn kind(&self) -> &'static str {
+        match self {
+            Node::Any => "any",
+            Node::Null => "null",
+            Node::Boolean => "boolean",
+            Node::Number(Number { integer: true, .. }) => "integer",
+            Node::Number(_) => "number",
+            Node::String(_) => "string",
+            Node::Array { .. } | Node::Tuple(_) => "array",
+            Node::Object(_) | Node::Record { .. } => "object",
+            Node::Union(_) => "union",
+        }
+    }
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+impl Number {
+    fn check(&self, n: &serde_json::Number, path: &Path, errors: &mut Vec<ValidationError>) {
+        if self.integer && !(n.is_i64() || n.is_u64()) {
+            errors.push(path.error("expected integer, found number"));
+            return;
+        }
+        if let Some(values) = &self.values {
+            if !n.as_i64().is_some_and(|n| values.contains(&n)) {
+                let values: Vec<String> = values.iter().map(i64::to_string).collect();
+                errors.push(path.error(format!("must be one of {}", values.join(", "))));
+            }
+        }
+        let n = n.as_f64().unwrap_or(f64::NAN);
+        if let Some(min) = self.min.filter(|min| n < *min) {
+            errors.push(path.error(format!("must be at least {min}")));
+        }
+        if let Some(max) = self.max.filter(|max| n > *max) {
+            errors.push(path.error(format!("must be at most {max}")));
+        }
+    }
+}
+
+impl Text {
+    fn check(&self, s: &str, path: &Path, errors: &mut Vec<ValidationError>) {
+        if self.len.min.is_some() || self.len.max.is_some() {
+            self.len
+                .check(s.chars().count(), "characters", path, errors);
+        }
+        if let Some(pattern) = self.pattern.as_ref().filter(|p| !p.is_match(s)) {
+            errors.push(path.error(format!("must match pattern `{}`", pattern.source)));
+        }
+        if let Some((name, _)) = self.format.as_ref().filter(|(_, f)| !f.is_match(s)) {
+            errors.push(path.error(format!("must be a valid {name}")));
+        }
+        if let Some(one_of) = self.one_of.as_ref().filter(|v| !v.iter().any(|v| v == s)) {
+            let quoted: Vec<String> = one_of.iter().map(|v| format!("'{v}'")).collect();
+            errors.push(path.error(format!("must be one of {}", quoted.join(", "))));
+        }
+    }
+}
+
+impl Length {
+    fn from(constraints: &Constraints) -> Length {
+        Length {
+            min: constraints.min_len,
+            max: constraints.max_len,
+        }
+    }
+
+    fn check(&self, len: usize, unit: &str, path: &Path, errors: &mut Vec<ValidationError>) {
+        if let Some(min) = self.min.filter(|min| len < *min) {
+            errors.push(path.error(format!("must have at least {min} {unit}")));
+        }
+        if let Some(max) = self.max.filter(|max| len > *max) {
+            errors.push(path.error(format!("must have at most {max} {unit}")));
+        }
+    }
+}
+
+impl Format {
+    fn is_match(&self, s: &str) -> bool {
+        match self {
+            Format::Pattern(pattern) => pattern.is_match(s),
+            Format::Ipv4 => s.parse::<std::net::Ipv4Addr>().is_ok(),
+            Format::Ipv6 => s.parse::<std::net::Ipv6Addr>().is_ok(),
+        }
+    }
+}
+
+impl Pattern {
+    fn new(source: &str) -> Result<Pattern, CompileError> {
+        let matcher =
+            match ascii_class(source) {
+                Some((ascii, min, max)) => Matcher::Class { ascii, min, max },
+                None => Matcher::Regex(Regex::new(source).map_err(|e| {
+                    CompileError::InvalidPattern {
+                        pattern: source.to_string(),
+                        message: e.to_string(),
+                    }
+                })?),
+            };
+        Ok(Pattern {
+            source: source.to_string(),
+            matcher,
+        })
+    }
+
+    fn is_match(&self, s: &str) -> bool {
+        match &self.matcher {
+            Matcher::Class { ascii, min, max } => {
+                s.len() >= *min
+                    && max.is_none_or(|max| s.len() <= max)
+                    && s.bytes().all(|b| b < 128 && ascii >> b & 1 == 1)
+            }
+            Matcher::Regex(regex) => regex.is_match(s),
+        }
+    }
+}
+
+/// Parse `^[class]quantifier$`, where the class holds only ASCII literals
+/// and ranges, into a bit set and a length range.
+fn ascii_class(pattern: &str) -> Option<(u128, usize, Option<usize>)> {
+    let (class, quantifier) = pattern
+        .strip_prefix("^[")?
+        .strip_suffix('$')?
+        .split_once(']')?;
+    if class.is_empty()
+        || !class.is_ascii()
+        || class.starts_with('^')
+        || ["\\", "[", "&&", "--", "~~"]
+            .iter()
+            .any(|special| class.contains(special))
+    {
+        return None;
+    }
+
+    let mut ascii = 0u128;
+    let mut rest = class.as_bytes();
+    while let Some((&lo, tail)) = rest.split_first() {
+        let (hi, tail) = match tail {
+            [b'-', hi, tail @ ..] => (*hi, tail),
+            _ => (lo, tail),
+        };
+        if lo > hi {
+            return None;
+        }
+        for b in lo..=hi {
+            ascii |= 1 << b;
+        }
+        rest = tail;
+    }
+
+    let (min, max) = match quantifier {
+        "" => (1, Some(1)),
+        "+" => (1, None),
+        "*" => (0, None),
+        _ => {
+            let counts = quantifier.strip_prefix('{')?.strip_suffix('}')?;
+            match counts.split_once(',') {
+                None => {
+                    let n = counts.parse().ok()?;
+                    (n, Some(n))
+                }
+                Some((min, "")) => (min.parse().ok()?, None),
+                Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+            }
+        }
+    };
+    Some((ascii, min, max))
+}
+
+/// FNV-1a: field names are short, so SipHash's setup would dominate lookups.
+struct FieldHasher(u64);
+
+impl Default for FieldHasher {
+    fn default() -> Self {
+        FieldHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FieldHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Patterns for the formats attached by this crate's impls. Other formats
+/// are descriptive only and not checked.
+fn format_pattern(format: &str) -> Option<&'static str> {
+    Some(match format {
+        "date-time" => {
+            r"^[+-]?\d{4,}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$"
+        }
+        "date-time-local" => r"^[+-]?\d{4,}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?$",
+        "date" => r"^[+-]?\d{4,}-\d{2}-\d{2}$",
+        "time" => r"^\d{2}:\d{2}:\d{2}(\.\d+)?$",
+        "uuid" => r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+        "uri" => r"^[A-Za-z][A-Za-z0-9+.-]*:\S*$",
+        "decimal" => r"^-?[0-9]+(\.[0-9]+)?$",
+        _ => return None,
+    })
+}
+
+const BASE64: &str = r"^[A-Za-z0-9+/_-]*={0,2}$";
+const HEX: &str = r"^([0-9a-fA-F]{2})*$";
+
+/// Location of a value, kept on the stack while walking and only turned into
+/// a string for an error.
+enum Path<'a> {
+    Root,
+    Key(&'a Path<'a>, &'a str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl Path<'_> {
+    fn error(&self, message: impl Into<String>) -> ValidationError {
+        let mut path = String::new();
+        self.write(&mut path);
+        ValidationError {
+            path,
+            message: message.into(),
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        match self {
+            Path::Root => {}
+            Path::Key(parent, key) => {
+                parent.write(out);
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(key);
+            }
+            Path::Index(parent, i) => {
+                parent.write(out);
+                out.push_str(&format!("[{i}]"));
+            }
+        }
+    }
+}
+
+struct Compiler<'a> {
+    nodes: Vec<Node>,
+    definitions: HashMap<&'a str, &'a Schema>,
+    resolved: HashMap<&'a str, NodeId>,
+    /// Nodes of `Shared` schemas, so a type nested in many places is
+    /// compiled once
+    shared: HashMap<*const Schema, NodeId>,
+    patterns: HashMap<String, Pattern>,
+    names: HashMap<NodeId, String>,
+}
+
+impl<'a> Compiler<'a> {
+    /// Compile `schema` with `constraints` inherited from enclosing
+    /// `Constrained` nodes and fields (outer ones take precedence).
+    fn compile(
+        &mut self,
+        schema: &'a Schema,
+        constraints: Constraints,
+    ) -> Result<NodeId, CompileError> {
+        let node = match schema {
+            // A copy of the node carries the name, leaving the unbranded
+            // one (which may be shared) as it is
+            Schema::Branded { name, schema } => {
+                let inner = self.compile(schema, constraints)?;
+                let id = self.push(self.nodes[inner].clone());
+                self.names.insert(id, name.clone());
+                return Ok(id);
+            }
+            Schema::Custom { schema, .. } | Schema::Ordered(schema) => {
+                return self.compile(schema, constraints)
+            }
+            Schema::Constrained {
+                schema,
+                constraints: inner,
+            } => return self.compile(schema, constraints.or(inner)),
+            Schema::Shared(schema) => {
+                if !constraints.is_empty() {
+                    return self.compile(schema, constraints);
+                }
+                let key: *const Schema = *schema;
+                if let Some(&id) = self.shared.get(&key) {
+                    return Ok(id);
+                }
+                let id = self.compile(schema, Constraints::default())?;
+                self.shared.insert(key, id);
+                return Ok(id);
+            }
+            Schema::Ref(name) => {
+                // Derived types nested in themselves refer to their own
+                // cached schema when not given as a definition
+                let definition = self
+                    .definitions
+                    .get(name.as_str())
+                    .copied()
+                    .or_else(|| cache::recursive(name))
+                    .ok_or_else(|| CompileError::UnresolvedRef(name.clone()))?;
+                if !constraints.is_empty() {
+                    return self.compile(definition, constraints);
+                }
+                if let Some(&id) = self.resolved.get(name.as_str()) {
+                    return Ok(id);
+                }
+                // Reserved before compiling so recursive references land here
+                let id = self.push(Node::Any);
+                self.resolved.insert(name, id);
+                let target = self.compile(definition, Constraints::default())?;
+                self.nodes[id] = self.nodes[target].clone();
+                if let Some(name) = self.names.get(&target).cloned() {
+                    self.names.insert(id, name);
+                }
+                return Ok(id);
+            }
+            Schema::Any => Node::Any,
+            Schema::Null => Node::Null,
+            Schema::Boolean => Node::Boolean,
+            Schema::Number | Schema::Integer => Node::Number(Number {
+                integer: *schema == Schema::Integer,
+                min: constraints.min,
+                max: constraints.max,
+                values: None,
+            }),
+            Schema::NumericEnum(values) => Node::Number(Number {
+                integer: true,
+                min: constraints.min,
+                max: constraints.max,
+                values: Some(values.clone()),
+            }),
+            Schema::String => Node::String(self.text(&constraints)?),
+            Schema::Enum(values) => Node::String(Text {
+                one_of: Some(match &constraints.one_of {
+                    Some(one_of) => values
+                        .iter()
+                        .filter(|v| one_of.contains(v))
+                        .cloned()
+                        .collect(),
+                    None => values.clone(),
+                }),
+                ..self.text(&Constraints {
+                    one_of: None,
+                    ..constraints
+                })?
+            }),
+            // Lengths of encoded bytes count characters, not bytes: not checked
+            Schema::Bytes(BytesEncoding::Base64) => Node::String(Text {
+                pattern: Some(self.pattern(BASE64)?),
+                ..Text::default()
+            }),
+            Schema::Bytes(BytesEncoding::Hex) => Node::String(Text {
+                pattern: Some(self.pattern(HEX)?),
+                ..Text::default()
+            }),
+            Schema::Bytes(BytesEncoding::Array) => {
+                let byte = self.push(Node::Number(Number {
+                    integer: true,
+                    min: Some(0.0),
+                    max: Some(255.0),
+                    values: None,
+                }));
+                Node::Array {
+                    item: byte,
+                    len: Length::from(&constraints),
+                }
+            }
+            Schema::Array(item) => Node::Array {
+                item: self.compile(item, Constraints::default())?,
+                len: Length::from(&constraints),
+            },
+            Schema::Tuple(items) => Node::Tuple(
+                items
+                    .iter()
+                    .map(|item| self.compile(item, Constraints::default()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Schema::Record { key, value, .. } => Node::Record {
+                key: self.compile(key, Constraints::default())?,
+                value: self.compile(value, Constraints::default())?,
+                len: Length::from(&constraints),
+            },
+            Schema::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        // Field-level constraints refine those carried by the field's type
+                        let node = self.compile(&field.schema, field.constraints.clone())?;
+                        Ok(ObjectField {
+                            name: field.name.clone(),
+                            node,
+                            required: field.required,
+                            nullable: field.nullable,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Node::Object(Object {
+                    index: fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, field)| (field.name.clone(), i))
+                        .collect(),
+                    required: fields.iter().filter(|field| field.required).count(),
+                    fields,
+                })
+            }
+            // Constraints on an `Option<T>` apply to the `T`
+            Schema::Union(members) => Node::Union(
+                members
+                    .iter()
+                    .map(|member| match member {
+                        Schema::Null => self.compile(member, Constraints::default()),
+                        _ => self.compile(member, constraints.clone()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(self.push(node))
+    }
+
+    fn text(&mut self, constraints: &Constraints) -> Result<Text, CompileError> {
+        let format = match constraints.format.as_deref() {
+            Some(name @ ("ipv4" | "ipv6")) => Some((
+                name.to_string(),
+                if name == "ipv4" {
+                    Format::Ipv4
+                } else {
+                    Format::Ipv6
+                },
+            )),
+            Some(name) => match format_pattern(name) {
+                Some(pattern) => Some((name.to_string(), Format::Pattern(self.pattern(pattern)?))),
+                None => None,
+            },
+            None => None,
+        };
+        Ok(Text {
+            len: Length::from(constraints),
+            pattern: constraints
+                .pattern
+                .as_deref()
+                .map(|pattern| self.pattern(pattern))
+                .transpose()?,
+            format,
+            one_of: constraints.one_of.clone(),
+        })
+    }
+
+    /// Each distinct pattern is compiled once per schema.
+    fn pattern(&mut self, source: &str) -> Result<Pattern, CompileError> {
+        if let Some(pattern) = self.patterns.get(source) {
+            return Ok(pattern.clone());
+        }
+        let pattern = Pattern::new(source)?;
+        self.patterns.insert(source.to_string(), pattern.clone());
+        Ok(pattern)
+    }
+
+    fn push(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, SchemaBridge};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::net::Ipv4Addr;
+
+    fn errors(schema: &Schema, value: Value) -> Vec<String> {
+        match CompiledSchema::compile(schema).unwrap().validate(&value) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn constrained(schema: Schema, constraints: Constraints) -> Schema {
+        schema.constrained(constraints)
+    }
+
+    #[test]
+    fn checks_types() {
+        assert!(errors(&Schema::String, json!("a")).is_empty());
+        assert_eq!(
+            errors(&Schema::String, json!(1)),
+            ["expected string, found number"]
+        );
+        assert_eq!(
+            errors(&Schema::Integer, json!(1.5)),
+            ["expected integer, found number"]
+        );
+        assert!(errors(&Schema::Number, json!(1)).is_empty());
+        assert!(errors(&Schema::Any, json!({ "a": [1] })).is_empty());
+    }
+
+    #[test]
+    fn checks_constraints() {
+        let age = constrained(
+            Schema::Integer,
+            Constraints {
+                min: Some(0.0),
+                max: Some(150.0),
+                ..Default::default()
+            },
+        );
+        assert!(errors(&age, json!(30)).is_empty());
+        assert_eq!(errors(&age, json!(-1)), ["must be at least 0"]);
+
+        let code = constrained(
+            Schema::String,
+            Constraints {
+                min_len: Some(2),
+                pattern: Some("^[a-z]+$".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            errors(&code, json!("A")),
+            [
+                "must have at least 2 characters",
+                "must match pattern `^[a-z]+$`"
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_formats() {
+        let ip = Ipv4Addr::to_schema();
+        assert!(errors(&ip, json!("127.0.0.1")).is_empty());
+        assert_eq!(errors(&ip, json!("localhost")), ["must be a valid ipv4"]);
+
+        let unknown = constrained(
+            Schema::String,
+            Constraints {
+                format: Some("email".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(errors(&unknown, json!("anything")).is_empty());
+    }
+
+    #[test]
+    fn reports_paths() {
+        let schema = Schema::Object(vec![
+            Field::new("name", Schema::String),
+            Field::new("tags", Vec::<u8>::to_schema()),
+        ]);
+        assert_eq!(
+            errors(&schema, json!({ "tags": [1, "x"] })),
+            [
+                "tags[1]: expected integer, found string",
+                "name: is required"
+            ]
+        );
+    }
+
+    #[test]
+    fn optional_and_nullable_fields() {
+        let schema = Schema::Object(vec![Field::optional("nickname", Schema::String)]);
+        assert!(errors(&schema, json!({})).is_empty());
+        assert!(errors(&schema, json!({ "nickname": null })).is_empty());
+        assert_eq!(
+            errors(&schema, json!({ "nickname": 1 })),
+            ["nickname: expected string, found number"]
+        );
+    }
+
+    #[test]
+    fn record_keys() {
+        let schema = HashMap::<u32, bool>::to_schema();
+        assert!(errors(&schema, json!({ "1": true })).is_empty());
+        assert_eq!(
+            errors(&schema, json!({ "x": true })),
+            ["x: invalid key: must match pattern `^[0-9]+$`"]
+        );
+    }
+
+    #[test]
+    fn unions_report_the_matching_variant() {
+        let schema = Option::<Vec<String>>::to_schema();
+        assert!(errors(&schema, json!(null)).is_empty());
+        assert_eq!(
+            errors(&schema, json!([1])),
+            ["[0]: expected string, found number"]
+        );
+        assert_eq!(
+            errors(&schema, json!(true)),
+            ["expected array | null, found boolean"]
+        );
+    }
+
+    #[test]
+    fn resolves_recursive_refs() {
+        let node = Schema::Object(vec![
+            Field::new("value", Schema::Integer),
+            Field::new(
+                "children",
+                Schema::Array(Box::new(Schema::Ref("Node".into()))),
+            ),
+        ]);
+        let definitions = [Declaration::new("Node", node)];
+        let compiled =
+            CompiledSchema::compile_with(&Schema::Ref("Node".into()), &definitions).unwrap();
+        let errors = compiled
+            .validate(&json!({ "value": 1, "children": [{ "value": "x", "children": [] }] }))
+            .unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "children[0].value: expected integer, found string"
+        );
+
+        assert_eq!(
+            CompiledSchema::compile(&Schema::Ref("Node".into())).unwrap_err(),
+            CompileError::UnresolvedRef("Node".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let schema = constrained(
+            Schema::String,
+            Constraints {
+                pattern: Some("(".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            CompiledSchema::compile(&schema),
+            Err(CompileError::InvalidPattern { .. })
+        ));
+    }
+
+    #[test]
+    fn simple_classes_skip_the_regex_engine() {
+        assert_eq!(ascii_class("^[0-9]+$"), Some((0x3ff << b'0', 1, None)));
+        assert_eq!(
+            ascii_class("^[a-c-]{2,4}$").map(|(_, min, max)| (min, max)),
+            Some((2, Some(4)))
+        );
+        assert_eq!(ascii_class(r"^[\d]+$"), None);
+        assert_eq!(ascii_class("^[^@]+$"), None);
+        assert_eq!(ascii_class("^[1-9][0-9]*$"), None);
+
+        let hex = Pattern::new("^[0-9a-f]{6}$").unwrap();
+        assert!(matches!(hex.matcher, Matcher::Class { .. }));
+        assert!(hex.is_match("f29513"));
+        assert!(!hex.is_match("F29513"));
+        assert!(!hex.is_match("f2951"));
+        assert!(!hex.is_match("f29513é"));
+    }
+
+    #[test]
+    fn validated_deserialization() {
+        assert_eq!(
+            from_value_validated::<Vec<u8>>(json!([1, 2])).unwrap(),
+            [1, 2]
+        );
+
+        let err = from_value_validated::<u8>(json!(300)).unwrap_err();
+        assert_eq!(err.to_string(), "invalid value: integer `300`, expected u8");
+        assert_eq!(err.errors()[0].path, "");
+
+        let err = from_str_validated::<Vec<String>>("[1]").unwrap_err();
+        assert_eq!(err.to_string(), "[0]: expected string, found number");
+    }
+
+    #[test]
+    fn bytes() {
+        assert!(errors(&Schema::Bytes(BytesEncoding::Hex), json!("0aff")).is_empty());
+        assert_eq!(
+            errors(&Schema::Bytes(BytesEncoding::Array), json!([256])),
+            ["[0]: must be at most 255"]
+        );
+    }
+}
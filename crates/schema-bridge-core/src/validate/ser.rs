@@ -0,0 +1,1139 @@
+//! Validation of Rust values by serializing them into a checker instead of a
+//! `serde_json::Value`. The serializer follows serde_json's data model, so a
+//! value passes exactly when its JSON would.
+
+use super::{CompiledSchema, Length, Node, NodeId, Object, Path, ValidationError};
+use serde::ser::{self, Impossible, Serialize};
+use std::fmt::{self, Write};
+
+/// Why a walk stopped early.
+#[derive(Debug)]
+pub(super) enum Interrupt {
+    /// A union with several members that could hold the value: the caller
+    /// falls back to checking the `serde_json::Value`.
+    Ambiguous,
+    /// The value's `Serialize` impl failed, or wrote something JSON cannot hold.
+    Failed(ValidationError),
+}
+
+impl fmt::Display for Interrupt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Interrupt::Ambiguous => f.write_str("ambiguous union"),
+            Interrupt::Failed(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Interrupt {}
+
+impl ser::Error for Interrupt {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Interrupt::Failed(Path::Root.error(msg.to_string()))
+    }
+}
+
+/// JSON kind written by a serializer call.
+#[derive(Clone, Copy)]
+enum Kind {
+    Null,
+    Boolean,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Boolean => "boolean",
+            Kind::Number => "number",
+            Kind::String => "string",
+            Kind::Array => "array",
+            Kind::Object => "object",
+        }
+    }
+}
+
+/// Checks one value against the node `id`.
+pub(super) struct Validator<'a> {
+    schema: &'a CompiledSchema,
+    id: NodeId,
+    /// An `Option<T>` field whose node describes the `T`
+    nullable: bool,
+    path: &'a Path<'a>,
+    errors: &'a mut Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    pub(super) fn new(
+        schema: &'a CompiledSchema,
+        id: NodeId,
+        path: &'a Path<'a>,
+        errors: &'a mut Vec<ValidationError>,
+    ) -> Self {
+        Validator {
+            schema,
+            id,
+            nullable: false,
+            path,
+            errors,
+        }
+    }
+
+    /// The node a value of `kind` is checked against, looking through
+    /// unions; `None` when there is nothing left to check. `variant` is the
+    /// key of an enum variant written as `{ variant: ... }`.
+    fn resolve(&mut self, kind: Kind, variant: Option<&str>) -> Result<Option<NodeId>, Interrupt> {
+        if self.nullable && matches!(kind, Kind::Null) {
+            return Ok(None);
+        }
+        let schema = self.schema;
+        let id = match &schema.nodes[self.id] {
+            Node::Union(members) => {
+                let mut candidates = members
+                    .iter()
+                    .filter(|&&id| schema.admits_kind(id, kind, variant));
+                match (candidates.next(), candidates.next()) {
+                    (Some(&only), None) => schema.flatten(only, kind, variant),
+                    (Some(_), Some(_)) => {
+                        return match members
+                            .iter()
+                            .any(|&id| matches!(schema.nodes[id], Node::Any))
+                        {
+                            true => Ok(None),
+                            false => Err(Interrupt::Ambiguous),
+                        }
+                    }
+                    (None, _) => self.id,
+                }
+            }
+            _ => self.id,
+        };
+        if schema.admits_kind(id, kind, variant) {
+            Ok(match schema.nodes[id] {
+                Node::Any => None,
+                _ => Some(id),
+            })
+        } else {
+            self.errors
+                .push(self.path.error(schema.mismatch(self.id, kind.name())));
+            Ok(None)
+        }
+    }
+
+    fn number(mut self, n: serde_json::Number) -> Result<(), Interrupt> {
+        if let Some(id) = self.resolve(Kind::Number, None)? {
+            if let Node::Number(number) = &self.schema.nodes[id] {
+                number.check(&n, self.path, self.errors);
+            }
+        }
+        Ok(())
+    }
+
+    /// A 128-bit integer, which JSON only holds within the 64-bit range.
+    fn wide(self, n: Option<serde_json::Number>) -> Result<(), Interrupt> {
+        match n {
+            Some(n) => self.number(n),
+            None => Err(Interrupt::Failed(self.path.error("number out of range"))),
+        }
+    }
+
+    fn null(mut self) -> Result<(), Interrupt> {
+        self.resolve(Kind::Null, None).map(drop)
+    }
+
+    fn compound(
+        mut self,
+        kind: Kind,
+        variant: Option<&'static str>,
+    ) -> Result<Compound<'a>, Interrupt> {
+        let schema = self.schema;
+        let mut nullable = false;
+        let id = match variant {
+            Some(variant) => match self.enter(variant)? {
+                Some((id, field_nullable)) => {
+                    nullable = field_nullable;
+                    Some(id)
+                }
+                None => None,
+            },
+            None => self.resolve(kind, None)?,
+        };
+        let state = match (id, variant) {
+            (Some(id), Some(variant)) => {
+                // The variant's content, e.g. the fields of a struct variant
+                let path = Path::Key(self.path, variant);
+                let mut inner = Validator {
+                    schema,
+                    id,
+                    nullable,
+                    path: &path,
+                    errors: &mut *self.errors,
+                };
+                inner
+                    .resolve(kind, None)?
+                    .map_or(State::Skip, |id| State::of(schema, id))
+            }
+            (Some(id), None) => State::of(schema, id),
+            (None, _) => State::Skip,
+        };
+        Ok(Compound {
+            schema,
+            path: self.path,
+            variant,
+            errors: self.errors,
+            state,
+        })
+    }
+
+    /// Check the `{ variant: ... }` wrapper of an enum variant, returning the
+    /// node of its content.
+    fn enter(&mut self, variant: &str) -> Result<Option<(NodeId, bool)>, Interrupt> {
+        let Some(id) = self.resolve(Kind::Object, Some(variant))? else {
+            return Ok(None);
+        };
+        let schema = self.schema;
+        Ok(match &schema.nodes[id] {
+            Node::Object(object) => {
+                for field in &object.fields {
+                    if field.required && field.name != variant {
+                        self.errors
+                            .push(Path::Key(self.path, &field.name).error("is required"));
+                    }
+                }
+                object
+                    .index
+                    .get(variant)
+                    .map(|&i| (object.fields[i].node, object.fields[i].nullable))
+            }
+            Node::Record { key, value, len } => {
+                len.check(1, "entries", self.path, self.errors);
+                schema.check_key(*key, variant, &Path::Key(self.path, variant), self.errors);
+                Some((*value, false))
+            }
+            _ => None,
+        })
+    }
+}
+
+impl CompiledSchema {
+    /// Whether node `id` could hold a value of `kind`. An object written as
+    /// an enum variant must have the variant's key.
+    fn admits_kind(&self, id: NodeId, kind: Kind, variant: Option<&str>) -> bool {
+        match (&self.nodes[id], kind) {
+            (Node::Any, _)
+            | (Node::Null, Kind::Null)
+            | (Node::Boolean, Kind::Boolean)
+            | (Node::Number(_), Kind::Number)
+            | (Node::String(_), Kind::String)
+            | (Node::Array { .. } | Node::Tuple(_), Kind::Array)
+            | (Node::Record { .. }, Kind::Object) => true,
+            (Node::Object(object), Kind::Object) => {
+                variant.is_none_or(|variant| object.index.contains_key(variant))
+            }
+            (Node::Union(members), _) => members
+                .iter()
+                .any(|&id| self.admits_kind(id, kind, variant)),
+            _ => false,
+        }
+    }
+
+    /// The single member of nested unions that admits `kind`.
+    fn flatten(&self, id: NodeId, kind: Kind, variant: Option<&str>) -> NodeId {
+        match &self.nodes[id] {
+            Node::Union(members) => members
+                .iter()
+                .find(|&&member| self.admits_kind(member, kind, variant))
+                .map_or(id, |&member| self.flatten(member, kind, variant)),
+            _ => id,
+        }
+    }
+}
+
+impl<'a> ser::Serializer for Validator<'a> {
+    type Ok = ();
+    type Error = Interrupt;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(mut self, _: bool) -> Result<(), Interrupt> {
+        self.resolve(Kind::Boolean, None).map(drop)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Interrupt> {
+        let n = i64::try_from(v).map(Into::into);
+        self.wide(n.or_else(|_| u64::try_from(v).map(Into::into)).ok())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Interrupt> {
+        self.number(v.into())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Interrupt> {
+        self.wide(u64::try_from(v).map(Into::into).ok())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Interrupt> {
+        self.serialize_f64(v.into())
+    }
+
+    // Non-finite floats are written as `null`
+    fn serialize_f64(self, v: f64) -> Result<(), Interrupt> {
+        match serde_json::Number::from_f64(v) {
+            Some(n) => self.number(n),
+            None => self.null(),
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Interrupt> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<(), Interrupt> {
+        if let Some(id) = self.resolve(Kind::String, None)? {
+            if let Node::String(text) = &self.schema.nodes[id] {
+                text.check(v, self.path, self.errors);
+            }
+        }
+        Ok(())
+    }
+
+    // Written as an array of numbers
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Interrupt> {
+        let mut seq = self.compound(Kind::Array, None)?;
+        for byte in v {
+            ser::SerializeSeq::serialize_element(&mut seq, byte)?;
+        }
+        ser::SerializeSeq::end(seq)
+    }
+
+    fn serialize_none(self) -> Result<(), Interrupt> {
+        self.null()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Interrupt> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Interrupt> {
+        self.null()
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Interrupt> {
+        self.null()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), Interrupt> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Interrupt> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        mut self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Interrupt> {
+        let Some((id, nullable)) = self.enter(variant)? else {
+            return Ok(());
+        };
+        value.serialize(Validator {
+            schema: self.schema,
+            id,
+            nullable,
+            path: &Path::Key(self.path, variant),
+            errors: self.errors,
+        })
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Array, None)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Array, None)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Array, None)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Array, Some(variant))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Object, None)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Object, None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, Interrupt> {
+        self.compound(Kind::Object, Some(variant))
+    }
+}
+
+/// Checks the elements or entries of an array or object.
+pub(super) struct Compound<'a> {
+    schema: &'a CompiledSchema,
+    path: &'a Path<'a>,
+    /// Key of the enclosing `{ variant: ... }` wrapper
+    variant: Option<&'static str>,
+    errors: &'a mut Vec<ValidationError>,
+    state: State<'a>,
+}
+
+enum State<'a> {
+    /// Nothing to check, e.g. `Any` or a value of the wrong kind
+    Skip,
+    Array {
+        item: NodeId,
+        len: Length,
+        count: usize,
+    },
+    Tuple {
+        items: &'a [NodeId],
+        count: usize,
+    },
+    Object {
+        object: &'a Object,
+        seen: Seen,
+        key: String,
+    },
+    Record {
+        key: NodeId,
+        value: NodeId,
+        len: Length,
+        count: usize,
+        buffer: String,
+    },
+}
+
+impl<'a> State<'a> {
+    fn of(schema: &'a CompiledSchema, id: NodeId) -> State<'a> {
+        match &schema.nodes[id] {
+            Node::Array { item, len } => State::Array {
+                item: *item,
+                len: *len,
+                count: 0,
+            },
+            Node::Tuple(items) => State::Tuple { items, count: 0 },
+            Node::Object(object) => State::Object {
+                object,
+                seen: Seen::new(object.fields.len()),
+                key: String::new(),
+            },
+            Node::Record { key, value, len } => State::Record {
+                key: *key,
+                value: *value,
+                len: *len,
+                count: 0,
+                buffer: String::new(),
+            },
+            _ => State::Skip,
+        }
+    }
+}
+
+/// Fields of an object written so far; allocates only past 64 fields.
+enum Seen {
+    Few(u64),
+    Many(Vec<bool>),
+}
+
+impl Seen {
+    fn new(fields: usize) -> Seen {
+        if fields <= 64 {
+            Seen::Few(0)
+        } else {
+            Seen::Many(vec![false; fields])
+        }
+    }
+
+    fn insert(&mut self, i: usize) {
+        match self {
+            Seen::Few(bits) => *bits |= 1 << i,
+            Seen::Many(seen) => seen[i] = true,
+        }
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        match self {
+            Seen::Few(bits) => bits >> i & 1 == 1,
+            Seen::Many(seen) => seen[i],
+        }
+    }
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        let variant = self.variant.map(|variant| Path::Key(self.path, variant));
+        let path = variant.as_ref().unwrap_or(self.path);
+        let (id, count) = match &mut self.state {
+            State::Array { item, count, .. } => (Some(*item), count),
+            State::Tuple { items, count } => (items.get(*count).copied(), count),
+            _ => return self.unchecked(value),
+        };
+        let index = *count;
+        *count += 1;
+        match id {
+            Some(id) => value.serialize(Validator::new(
+                self.schema,
+                id,
+                &Path::Index(path, index),
+                self.errors,
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Interrupt> {
+        let variant = self.variant.map(|variant| Path::Key(self.path, variant));
+        let path = variant.as_ref().unwrap_or(self.path);
+        // Keys serde_json cannot write fail even where nothing is checked
+        let mut scratch = String::new();
+        let buffer = match &mut self.state {
+            State::Object { key, .. } => key,
+            State::Record { buffer, .. } => buffer,
+            _ => &mut scratch,
+        };
+        buffer.clear();
+        key.serialize(KeyWriter(buffer))
+            .map_err(|error| Interrupt::Failed(path.error(error.to_string())))
+    }
+
+    /// Check `value` under `key`, or under the key last written by
+    /// [`Compound::key`] when `None`.
+    fn entry<T: Serialize + ?Sized>(
+        &mut self,
+        key: Option<&str>,
+        value: &T,
+    ) -> Result<(), Interrupt> {
+        let variant = self.variant.map(|variant| Path::Key(self.path, variant));
+        let path = variant.as_ref().unwrap_or(self.path);
+        match &mut self.state {
+            State::Skip | State::Array { .. } | State::Tuple { .. } => self.unchecked(value),
+            State::Object {
+                object,
+                seen,
+                key: written,
+            } => {
+                let name = key.unwrap_or(written);
+                // Unknown keys pass, as serde ignores them by default
+                let Some(&i) = object.index.get(name) else {
+                    return self.unchecked(value);
+                };
+                seen.insert(i);
+                let field = &object.fields[i];
+                value.serialize(Validator {
+                    schema: self.schema,
+                    id: field.node,
+                    nullable: field.nullable,
+                    path: &Path::Key(path, name),
+                    errors: self.errors,
+                })
+            }
+            State::Record {
+                key: key_id,
+                value: value_id,
+                count,
+                buffer,
+                ..
+            } => {
+                *count += 1;
+                let name = key.unwrap_or(buffer);
+                let path = Path::Key(path, name);
+                self.schema.check_key(*key_id, name, &path, self.errors);
+                value.serialize(Validator::new(self.schema, *value_id, &path, self.errors))
+            }
+        }
+    }
+
+    /// Serialize `value` against `Any`, which still fails on what serde_json
+    /// cannot write, such as a map key that is not a string.
+    fn unchecked<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        value.serialize(Validator::new(
+            self.schema,
+            self.schema.any,
+            self.path,
+            self.errors,
+        ))
+    }
+
+    fn finish(self) -> Result<(), Interrupt> {
+        let variant = self.variant.map(|variant| Path::Key(self.path, variant));
+        let path = variant.as_ref().unwrap_or(self.path);
+        match self.state {
+            State::Skip => {}
+            State::Array { len, count, .. } => len.check(count, "items", path, self.errors),
+            State::Tuple { items, count } => {
+                if items.len() != count {
+                    self.errors
+                        .push(path.error(format!("expected {} items, found {count}", items.len())));
+                }
+            }
+            State::Object { object, seen, .. } => {
+                for (i, field) in object.fields.iter().enumerate() {
+                    if field.required && !seen.contains(i) {
+                        self.errors
+                            .push(Path::Key(path, &field.name).error("is required"));
+                    }
+                }
+            }
+            State::Record { len, count, .. } => len.check(count, "entries", path, self.errors),
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Interrupt> {
+        self.key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Interrupt> {
+        self.entry(None, value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Interrupt> {
+        self.entry(Some(key), value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Interrupt;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Interrupt> {
+        self.entry(Some(key), value)
+    }
+
+    fn end(self) -> Result<(), Interrupt> {
+        self.finish()
+    }
+}
+
+/// Writes a map key the way serde_json does: strings as-is, integers,
+/// booleans and unit variants as their text, and finite floats as serde_json
+/// writes the number (`1.0`, not `1`).
+struct KeyWriter<'a>(&'a mut String);
+
+/// A map key serde_json cannot write, with serde_json's message.
+#[derive(Debug)]
+struct BadKey(&'static str);
+
+const NOT_A_STRING: BadKey = BadKey("key must be a string");
+
+impl fmt::Display for BadKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for BadKey {}
+
+impl ser::Error for BadKey {
+    fn custom<T: fmt::Display>(_: T) -> Self {
+        NOT_A_STRING
+    }
+}
+
+impl KeyWriter<'_> {
+    fn display(self, v: impl fmt::Display) -> Result<(), BadKey> {
+        write!(self.0, "{v}").map_err(|_| NOT_A_STRING)
+    }
+
+    fn float<F: Serialize>(self, v: F, finite: bool) -> Result<(), BadKey> {
+        if !finite {
+            return Err(BadKey("float key must be finite (got NaN or +/-inf)"));
+        }
+        let text = serde_json::to_string(&v).map_err(|_| NOT_A_STRING)?;
+        self.0.push_str(&text);
+        Ok(())
+    }
+}
+
+impl ser::Serializer for KeyWriter<'_> {
+    type Ok = ();
+    type Error = BadKey;
+    type SerializeSeq = Impossible<(), BadKey>;
+    type SerializeTuple = Impossible<(), BadKey>;
+    type SerializeTupleStruct = Impossible<(), BadKey>;
+    type SerializeTupleVariant = Impossible<(), BadKey>;
+    type SerializeMap = Impossible<(), BadKey>;
+    type SerializeStruct = Impossible<(), BadKey>;
+    type SerializeStructVariant = Impossible<(), BadKey>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), BadKey> {
+        self.display(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), BadKey> {
+        self.float(v, v.is_finite())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), BadKey> {
+        self.float(v, v.is_finite())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), BadKey> {
+        self.0.push(v);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), BadKey> {
+        self.0.push_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_none(self) -> Result<(), BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<(), BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_unit(self) -> Result<(), BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), BadKey> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), BadKey> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, BadKey> {
+        Err(NOT_A_STRING)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, BadKey> {
+        Err(NOT_A_STRING)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompiledSchema, Constraints, Field, Schema, SchemaBridge, ValidationError};
+    use serde::Serialize;
+    use std::collections::{BTreeMap, HashMap};
+
+    /// Errors from the serializer, checked against the `serde_json::Value` walk.
+    fn errors<T: Serialize>(schema: &Schema, value: &T) -> Vec<String> {
+        let compiled = CompiledSchema::compile(schema).unwrap();
+        let direct = compiled.validate_value(value);
+        let via_json = compiled.validate(&serde_json::to_value(value).unwrap());
+        let sorted = |result: Result<(), Vec<ValidationError>>| {
+            let mut messages: Vec<String> = result
+                .err()
+                .unwrap_or_default()
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            messages.sort();
+            messages
+        };
+        let direct = sorted(direct);
+        assert_eq!(direct, sorted(via_json));
+        direct
+    }
+
+    fn range(min: f64, max: f64) -> Constraints {
+        Constraints {
+            min: Some(min),
+            max: Some(max),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scalars() {
+        let percent = Schema::Integer.constrained(range(0.0, 100.0));
+        assert!(errors(&percent, &42u8).is_empty());
+        assert_eq!(errors(&percent, &101i64), ["must be at most 100"]);
+        assert_eq!(
+            errors(&percent, &1.5f64),
+            ["expected integer, found number"]
+        );
+        assert_eq!(
+            errors(&Schema::String, &true),
+            ["expected string, found boolean"]
+        );
+        assert!(errors(&Schema::String, &'x').is_empty());
+        assert!(errors(&Schema::Null, &f64::NAN).is_empty());
+    }
+
+    #[test]
+    fn objects() {
+        #[derive(Serialize)]
+        struct User {
+            name: &'static str,
+            age: u8,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            email: Option<&'static str>,
+        }
+
+        let mut age = Field::new("age", Schema::Integer);
+        age.constraints = range(0.0, 150.0);
+        let email = Field::optional("email", Schema::String);
+        let schema = Schema::Object(vec![
+            Field::new("name", Schema::String),
+            Field::new("id", Schema::Integer),
+            age,
+            email,
+        ]);
+
+        let user = User {
+            name: "Ann",
+            age: 200,
+            email: None,
+        };
+        assert_eq!(
+            errors(&schema, &user),
+            ["age: must be at most 150", "id: is required"]
+        );
+    }
+
+    #[test]
+    fn collections_and_keys() {
+        let schema = Vec::<HashMap<u32, String>>::to_schema();
+        let ok = vec![HashMap::from([(1u32, "a".to_string())])];
+        assert!(errors(&schema, &ok).is_empty());
+
+        let bad = vec![BTreeMap::from([("x", 1)])];
+        assert_eq!(
+            errors(&schema, &bad),
+            [
+                "[0].x: expected string, found number",
+                "[0].x: invalid key: must match pattern `^[0-9]+$`",
+            ]
+        );
+        assert_eq!(
+            errors(&<(u8, u8)>::to_schema(), &(1, 2, 3)),
+            ["expected 2 items, found 3"]
+        );
+    }
+
+    #[test]
+    fn options_and_results() {
+        let schema = Option::<Result<u8, String>>::to_schema();
+        assert!(errors(&schema, &None::<Result<u8, String>>).is_empty());
+        assert!(errors(&schema, &Some(Ok::<u8, String>(1))).is_empty());
+        assert_eq!(
+            errors(&schema, &Some(Err::<String, u8>(1))),
+            ["Err: expected string, found number"]
+        );
+    }
+
+    #[test]
+    fn ambiguous_unions_fall_back_to_json() {
+        let schema = Schema::Union(vec![
+            Schema::String.constrained(Constraints {
+                max_len: Some(2),
+                ..Default::default()
+            }),
+            Schema::Enum(vec!["long".to_string()]),
+        ]);
+        assert!(errors(&schema, &"ab").is_empty());
+        assert!(errors(&schema, &"long").is_empty());
+        assert_eq!(errors(&schema, &"other"), ["does not match any variant"]);
+    }
+
+    /// A map with float keys, which std maps cannot hold
+    struct FloatKeys(Vec<(f64, u8)>);
+
+    impl Serialize for FloatKeys {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+        }
+    }
+
+    #[test]
+    fn float_keys_are_written_like_serde_json() {
+        let schema = Schema::Record {
+            key: Box::new(Schema::String.constrained(Constraints {
+                pattern: Some("^[0-9]+$".to_string()),
+                ..Default::default()
+            })),
+            value: Box::new(Schema::Integer),
+        };
+        assert_eq!(
+            errors(&schema, &FloatKeys(vec![(1.0, 1), (2.5, 2)])),
+            [
+                "1.0: invalid key: must match pattern `^[0-9]+$`",
+                "2.5: invalid key: must match pattern `^[0-9]+$`",
+            ]
+        );
+
+        let compiled = CompiledSchema::compile(&Schema::Any).unwrap();
+        let errors = compiled
+            .validate_value(&FloatKeys(vec![(f64::NAN, 1)]))
+            .unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "float key must be finite (got NaN or +/-inf)"
+        );
+    }
+
+    #[test]
+    fn non_string_keys_fail() {
+        let compiled = CompiledSchema::compile(&Schema::Any).unwrap();
+        let errors = compiled
+            .validate_value(&HashMap::from([((), 1)]))
+            .unwrap_err();
+        assert_eq!(errors[0].to_string(), "key must be a string");
+        let nested = vec![Some(HashMap::from([((), 1)]))];
+        assert!(compiled.validate_value(&nested).is_err());
+
+        let compiled = CompiledSchema::compile(&HashMap::<String, u8>::to_schema()).unwrap();
+        let errors = compiled
+            .validate_value(&HashMap::from([((1, 2), 1)]))
+            .unwrap_err();
+        assert_eq!(errors[0].to_string(), "key must be a string");
+    }
+}
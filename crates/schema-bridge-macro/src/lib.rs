@@ -1,38 +1,82 @@
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
 use syn::{
-    parse::Parse, parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Fields, Ident, Lit,
-    Meta, Token, Type,
+    parse::Parse, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
+    Fields, Ident, Lit, Token, Type,
 };
 
 #[proc_macro_derive(SchemaBridge, attributes(schema_bridge, schema, serde))]
 pub fn derive_schema_bridge(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let ts_impl = impl_to_ts(&input);
-    let schema_impl = impl_to_schema(name, &input);
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
 
-    // Check for string_conversion attribute
-    let string_conversion = has_string_conversion(&input.attrs);
+    let container = parse_container_attrs(&input.attrs)?;
+    if let (Some(span), false) = (container.numeric, matches!(input.data, Data::Enum(_))) {
+        return Err(syn::Error::new(
+            span,
+            "SchemaBridge: `numeric` is only supported on enums",
+        ));
+    }
 
-    let mut expanded = quote! {
-        impl ::schema_bridge::SchemaBridge for #name {
-            fn to_ts() -> String {
-                #ts_impl
+    let schema_impl = impl_schema(input, &container, Mode::Output)?;
+    let input_impl = impl_schema(input, &container, Mode::Input)?;
+    // Only asymmetric types need their own `to_schema_input()`
+    let input_impl = if input_impl.to_string() == schema_impl.to_string() {
+        quote! {}
+    } else {
+        let input_impl = guard_recursion(input, Mode::Input, input_impl);
+        quote! {
+            fn to_schema_input() -> ::schema_bridge::Schema {
+                #input_impl
             }
+        }
+    };
+    let schema_impl = guard_recursion(input, Mode::Output, schema_impl);
+    let cache_impl = impl_schema_cache(input);
+    let declarations_impl = impl_ts_declarations(input, &container)?;
+    let companions_impl = impl_companions(input, &container)?;
 
+    // Every type parameter has to describe itself for the fields to
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(syn::parse_quote!(::schema_bridge::SchemaBridge));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let key_impl = impl_schema_key(input, &container, &generics)?;
+
+    let mut expanded = quote! {
+        // `to_ts()` renders `to_schema()`, cached per type with the schema
+        impl #impl_generics ::schema_bridge::SchemaBridge for #name #ty_generics #where_clause {
             fn to_schema() -> ::schema_bridge::Schema {
                 #schema_impl
             }
+
+            #input_impl
+
+            #cache_impl
+
+            #declarations_impl
+
+            #companions_impl
         }
+
+        #key_impl
     };
 
     // Generate Display and FromStr if requested
-    if string_conversion {
+    if container.string_conversion {
         if let Data::Enum(_) = &input.data {
-            let display_impl = impl_display(&input);
-            let fromstr_impl = impl_fromstr(&input);
+            let display_impl = impl_display(input);
+            let fromstr_impl = impl_fromstr(input);
 
             expanded = quote! {
                 #expanded
@@ -44,130 +88,635 @@ pub fn derive_schema_bridge(input: TokenStream) -> TokenStream {
         }
     }
 
-    TokenStream::from(expanded)
+    Ok(expanded)
 }
 
-/// Check if #[schema_bridge(string_conversion)] attribute is present
-fn has_string_conversion(attrs: &[syn::Attribute]) -> bool {
-    for attr in attrs {
-        if attr.path().is_ident("schema_bridge") {
-            if let Meta::List(meta_list) = &attr.meta {
-                if let Ok(Meta::Path(path)) = syn::parse2(meta_list.tokens.clone()) {
-                    if path.is_ident("string_conversion") {
-                        return true;
+/// `body` run through `cache::build`, which stands in a `Schema::Ref` for
+/// the type wherever it is nested in itself. Generic types are left as they
+/// are, like in [`impl_schema_cache`].
+fn guard_recursion(
+    input: &DeriveInput,
+    mode: Mode,
+    body: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if !input.generics.params.is_empty() {
+        return body;
+    }
+    let name = input.ident.to_string();
+    let input = matches!(mode, Mode::Input);
+    quote! {
+        ::schema_bridge::cache::build::<Self>(#input, #name, || { #body })
+    }
+}
+
+/// Per-type statics behind `schema_ref()` / `schema_input_ref()` and
+/// `to_ts()`, skipping the trait's shared cache, and `shared_schema()`
+/// references into them. Generic types keep the defaults, since a static in
+/// a generic impl would be shared by every instantiation.
+fn impl_schema_cache(input: &DeriveInput) -> proc_macro2::TokenStream {
+    if !input.generics.params.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        fn schema_ref() -> &'static ::schema_bridge::Schema {
+            static SCHEMA: ::std::sync::OnceLock<&'static ::schema_bridge::Schema> =
+                ::std::sync::OnceLock::new();
+            ::schema_bridge::cache::output::<Self>(&SCHEMA)
+        }
+
+        fn schema_input_ref() -> &'static ::schema_bridge::Schema {
+            // Types read and written alike share one tree
+            static SCHEMA: ::std::sync::OnceLock<&'static ::schema_bridge::Schema> =
+                ::std::sync::OnceLock::new();
+            ::schema_bridge::cache::input::<Self>(&SCHEMA)
+        }
+
+        fn shared_schema() -> ::schema_bridge::Schema {
+            ::schema_bridge::cache::shared::<Self>(false)
+        }
+
+        fn shared_schema_input() -> ::schema_bridge::Schema {
+            ::schema_bridge::cache::shared::<Self>(true)
+        }
+
+        fn to_ts() -> ::std::string::String {
+            static TS: ::std::sync::OnceLock<::std::string::String> =
+                ::std::sync::OnceLock::new();
+            TS.get_or_init(|| {
+                ::schema_bridge::render_ts(
+                    <Self as ::schema_bridge::SchemaBridge>::schema_ref(),
+                    &::schema_bridge::TsConfig::default(),
+                )
+            })
+            .clone()
+        }
+    }
+}
+
+/// `SchemaKey` for types serde can write as a map key: unit-only enums,
+/// newtypes over a key type, and `into` proxies that are key types.
+///
+/// The inner bounds use a dummy higher-ranked lifetime so that a newtype over
+/// a non-key type (say `Vec<u8>`) still compiles, and only fails when used
+/// as a key.
+fn impl_schema_key(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    generics: &syn::Generics,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let name = &input.ident;
+    let inner = match (serde_proxy(&input.attrs, Mode::Output)?, &input.data) {
+        (Some(proxy), _) => Some(proxy),
+        (None, Data::Enum(data)) => {
+            // Tagged and untagged unit variants serialize as objects or
+            // null, which serde cannot write as a key
+            let tagged = ["tag", "untagged"]
+                .iter()
+                .any(|key| has_serde_key(&input.attrs, key));
+            if tagged
+                || !data
+                    .variants
+                    .iter()
+                    .all(|v| matches!(v.fields, Fields::Unit))
+            {
+                return Ok(None);
+            }
+            None
+        }
+        (None, Data::Struct(_)) => match newtype_field(input) {
+            Some(field) => Some(field.ty.clone()),
+            None => return Ok(None),
+        },
+        (None, Data::Union(_)) => return Ok(None),
+    };
+
+    let mut generics = generics.clone();
+    let body = match (inner, &input.data) {
+        (Some(inner), _) => {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(for<'__key> #inner: ::schema_bridge::SchemaKey));
+            quote! {
+                fn to_key_schema() -> ::schema_bridge::Schema {
+                    ::schema_bridge::key::newtype_key_schema::<Self, #inner>()
+                }
+            }
+        }
+        (None, Data::Enum(data)) => match numeric_discriminants(container, data) {
+            Some(values) => {
+                let values = values?;
+                quote! {
+                    fn to_key_schema() -> ::schema_bridge::Schema {
+                        ::schema_bridge::key::numeric_enum_key_schema(&[#(#values),*])
                     }
                 }
             }
+            None => quote! {},
+        },
+        (None, _) => unreachable!(),
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(Some(quote! {
+        impl #impl_generics ::schema_bridge::SchemaKey for #name #ty_generics #where_clause {
+            #body
+        }
+    }))
+}
+
+/// Which side of serde a schema describes.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// What the type serializes to, `to_schema()`
+    Output,
+    /// What the type accepts when deserialized, `to_schema_input()`
+    Input,
+}
+
+impl Mode {
+    fn other(self) -> Mode {
+        match self {
+            Mode::Output => Mode::Input,
+            Mode::Input => Mode::Output,
+        }
+    }
+
+    /// The `SchemaBridge` method giving this side of a type's schema.
+    fn method(self) -> proc_macro2::TokenStream {
+        match self {
+            Mode::Output => quote! { to_schema },
+            Mode::Input => quote! { to_schema_input },
+        }
+    }
+
+    /// The `SchemaBridge` method giving this side of a nested type's schema.
+    fn shared_method(self) -> proc_macro2::TokenStream {
+        match self {
+            Mode::Output => quote! { shared_schema },
+            Mode::Input => quote! { shared_schema_input },
+        }
+    }
+}
+
+/// Full body of `to_schema()` or `to_schema_input()`.
+fn impl_schema(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    mode: Mode,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // `into` / `from` / `try_from` types describe what actually goes on the wire
+    let proxy = serde_proxy(&input.attrs, mode)?;
+    let mut schema_impl = match &proxy {
+        Some(proxy) => {
+            let method = mode.method();
+            quote! { <#proxy as ::schema_bridge::SchemaBridge>::#method() }
+        }
+        None => impl_to_schema(&input.ident, input, container, mode)?,
+    };
+    // Newtypes merge container constraints with their field's in `impl_newtype`
+    if !is_newtype(input) || proxy.is_some() {
+        let attrs = parse_schema_attrs(&input.attrs)?;
+        reject_keys(&attrs, OVERRIDE_KEYS, "types")?;
+        match &proxy {
+            // Constraints describe the proxy type's values
+            Some(proxy) => {
+                check_applicable(&attrs, proxy)?;
+                schema_impl = constrain(constrain_elements(schema_impl, &attrs), &attrs);
+            }
+            // Only the other side goes through a proxy
+            None if serde_proxy(&input.attrs, mode.other())?.is_some() => {}
+            None => reject_keys(&attrs, SCHEMA_KEYS, container_kind(input))?,
         }
     }
-    false
+    if container.brand {
+        schema_impl = impl_brand(input, schema_impl)?;
+    }
+    if container.ts_enum.is_some() {
+        // Inline uses name the declared TS enum instead of repeating its values
+        let name = input.ident.to_string();
+        schema_impl = quote! {
+            ::schema_bridge::Schema::Custom {
+                ts: #name.to_string(),
+                schema: Box::new({ #schema_impl }),
+            }
+        };
+    }
+    Ok(schema_impl)
 }
 
-fn impl_to_ts(input: &DeriveInput) -> proc_macro2::TokenStream {
-    match &input.data {
-        Data::Struct(data) => {
-            match &data.fields {
-                Fields::Named(fields) => {
-                    // Check for serde rename_all attribute on struct
-                    let rename_all = get_serde_rename_all(&input.attrs);
-
-                    let fields_ts = fields.named.iter().map(|f| {
-                        let field_name = &f.ident;
-                        let field_str = field_name.as_ref().unwrap().to_string();
-                        let ty = &f.ty;
-
-                        // Apply rename_all transformation if present
-                        let ts_field_name = if let Some(ref rule) = rename_all {
-                            apply_rename_rule(&field_str, rule)
-                        } else {
-                            field_str
-                        };
+/// Options from #[schema_bridge(...)] on the type itself.
+///
+/// Supported: string_conversion, ts_enum, ts_const_values, brand, numeric, and companion
+/// types `partial = "Name"` / `companion = "Name"` with `pick(..)` / `omit(..)`
+#[derive(Default)]
+struct ContainerAttrs {
+    string_conversion: bool,
+    brand: bool,
+    /// Span of the `numeric` key, if given: serialized by discriminant value,
+    /// as `serde_repr` does
+    numeric: Option<Span>,
+    /// Span of the `ts_enum` key, if given
+    ts_enum: Option<Span>,
+    /// Span of the `ts_const_values` key, if given
+    ts_const_values: Option<Span>,
+    companions: Vec<Companion>,
+}
 
-                        quote! {
-                            format!("{}: {};", #ts_field_name, <#ty as ::schema_bridge::SchemaBridge>::to_ts())
-                        }
-                    });
+/// A named type derived from a struct's fields, one per `#[schema_bridge(...)]`.
+struct Companion {
+    name: syn::LitStr,
+    /// All fields optional; based on the input shape since it is sent to us
+    partial: bool,
+    pick: Option<Vec<Ident>>,
+    omit: Vec<Ident>,
+}
 
-                    quote! {
-                        let fields = vec![#(#fields_ts),*];
-                        format!("{{ {} }}", fields.join(" "))
-                    }
-                }
-                Fields::Unnamed(fields) => {
-                    // Support for tuple structs, especially newtype pattern
-                    if fields.unnamed.len() == 1 {
-                        // Newtype pattern: delegate to the inner type
-                        let inner_ty = &fields.unnamed[0].ty;
-                        quote! {
-                            <#inner_ty as ::schema_bridge::SchemaBridge>::to_ts()
-                        }
-                    } else {
-                        // Multiple field tuple struct - represent as tuple
-                        let field_types = fields.unnamed.iter().map(|f| {
-                            let ty = &f.ty;
-                            quote! {
-                                <#ty as ::schema_bridge::SchemaBridge>::to_ts()
-                            }
-                        });
+fn parse_container_attrs(attrs: &[syn::Attribute]) -> syn::Result<ContainerAttrs> {
+    let mut result = ContainerAttrs::default();
 
-                        quote! {
-                            let types = vec![#(#field_types),*];
-                            format!("[{}]", types.join(", "))
-                        }
+    for attr in attrs {
+        if !attr.path().is_ident("schema_bridge") {
+            continue;
+        }
+        // pick / omit apply to the companion named in the same attribute
+        let mut name: Option<(syn::LitStr, bool)> = None;
+        let mut pick: Option<Vec<Ident>> = None;
+        let mut omit = Vec::new();
+        attr.parse_nested_meta(|meta| {
+            for (key, partial) in [("partial", true), ("companion", false)] {
+                if meta.path.is_ident(key) {
+                    if name.is_some() {
+                        return Err(meta.error(
+                            "SchemaBridge: declare one companion per #[schema_bridge(...)] attribute",
+                        ));
                     }
+                    name = Some((meta.value()?.parse()?, partial));
+                    return Ok(());
+                }
+            }
+            if meta.path.is_ident("pick") || meta.path.is_ident("omit") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let fields = content.parse_terminated(Ident::parse, Token![,])?;
+                if meta.path.is_ident("pick") {
+                    pick.get_or_insert_with(Vec::new).extend(fields);
+                } else {
+                    omit.extend(fields);
                 }
-                Fields::Unit => quote! { "null".to_string() },
+                return Ok(());
+            }
+            if meta.path.is_ident("string_conversion") {
+                result.string_conversion = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("brand") {
+                result.brand = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("numeric") {
+                result.numeric = Some(meta.path.span());
+                return Ok(());
+            }
+            if meta.path.is_ident("ts_enum") {
+                result.ts_enum = Some(meta.path.span());
+                return Ok(());
             }
+            if meta.path.is_ident("ts_const_values") {
+                result.ts_const_values = Some(meta.path.span());
+                return Ok(());
+            }
+            Err(unknown_key(
+                &meta,
+                "schema_bridge",
+                "string_conversion, brand, numeric, ts_enum, ts_const_values, partial, companion, pick, omit",
+            ))
+        })?;
+
+        match name {
+            Some((name, partial)) => result.companions.push(Companion {
+                name,
+                partial,
+                pick,
+                omit,
+            }),
+            None if pick.is_some() || !omit.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "SchemaBridge: `pick` / `omit` need a `partial = \"...\"` or `companion = \"...\"` in the same attribute",
+                ));
+            }
+            None => {}
         }
-        Data::Enum(data) => {
-            // Check for serde rename_all attribute
-            let rename_all = get_serde_rename_all(&input.attrs);
+    }
 
-            let variants = data.variants.iter().map(|v| {
-                let variant_name = &v.ident;
-                let variant_str = variant_name.to_string();
+    Ok(result)
+}
 
-                // Apply rename_all transformation if present
-                let ts_name = if let Some(ref rule) = rename_all {
-                    apply_rename_rule(&variant_str, rule)
-                } else {
-                    variant_str
-                };
+/// Override `companion_declarations()` for `partial` / `companion` types.
+fn impl_companions(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if container.companions.is_empty() {
+        return Ok(quote! {});
+    }
+    let fields = match &input.data {
+        Data::Struct(data) if matches!(data.fields, Fields::Named(_)) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &container.companions[0].name,
+                "SchemaBridge: companion types are only supported on structs with named fields",
+            ))
+        }
+    };
+    let rename_all = get_serde_rename_all(&input.attrs);
+    let container_default = has_serde_key(&input.attrs, "default");
 
-                quote! {
-                    format!("'{}'", #ts_name)
-                }
-            });
+    let mut declarations = Vec::new();
+    for companion in &container.companions {
+        let mode = if companion.partial {
+            Mode::Input
+        } else {
+            Mode::Output
+        };
+        // Fields are named by their Rust ident; the schema knows them by their wire name
+        let wire_names = |idents: &[Ident]| -> syn::Result<Vec<String>> {
+            idents
+                .iter()
+                .map(|ident| {
+                    let field = fields
+                        .iter()
+                        .find(|f| f.ident.as_ref() == Some(ident))
+                        .ok_or_else(|| {
+                            syn::Error::new_spanned(
+                                ident,
+                                format!("SchemaBridge: no field `{ident}` in `{}`", input.ident),
+                            )
+                        })?;
+                    let field =
+                        analyze_named_field(field, rename_all.as_deref(), container_default)?;
+                    Ok(field.side(mode).name.clone())
+                })
+                .collect()
+        };
 
-            quote! {
-                let variants = vec![#(#variants),*];
-                variants.join(" | ")
-            }
+        let method = mode.method();
+        let mut schema = quote! { <Self as ::schema_bridge::SchemaBridge>::#method() };
+        if let Some(pick) = &companion.pick {
+            let names = wire_names(pick)?;
+            schema = quote! { #schema.pick(&[#(#names),*]) };
+        }
+        if !companion.omit.is_empty() {
+            let names = wire_names(&companion.omit)?;
+            schema = quote! { #schema.omit(&[#(#names),*]) };
+        }
+        if companion.partial {
+            schema = quote! { #schema.partial() };
+        }
+        let name = &companion.name;
+        declarations.push(quote! { ::schema_bridge::Declaration::new(#name, #schema) });
+    }
+
+    Ok(quote! {
+        fn companion_declarations() -> Vec<::schema_bridge::Declaration> {
+            vec![#(#declarations),*]
         }
-        _ => quote! { "any".to_string() },
+    })
+}
+
+/// Error for an unrecognized key inside `#[<attr>(...)]`, listing the valid ones.
+fn unknown_key(meta: &syn::meta::ParseNestedMeta, attr: &str, expected: &str) -> syn::Error {
+    let key = meta
+        .path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| {
+            let path = &meta.path;
+            quote!(#path).to_string()
+        });
+    meta.error(format!(
+        "SchemaBridge: unknown {attr} attribute `{key}`; expected one of: {expected}"
+    ))
+}
+
+fn is_newtype(input: &DeriveInput) -> bool {
+    newtype_field(input).is_some()
+}
+
+/// The wrapped field of a single-field tuple struct or a `#[serde(transparent)]`
+/// struct, which serializes as that field alone.
+fn newtype_field(input: &DeriveInput) -> Option<&syn::Field> {
+    let Data::Struct(data) = &input.data else {
+        return None;
+    };
+    if let Fields::Unnamed(fields) = &data.fields {
+        if fields.unnamed.len() == 1 {
+            return fields.unnamed.first();
+        }
+    }
+    if !has_serde_key(&input.attrs, "transparent") {
+        return None;
+    }
+    // serde ignores skipped fields and `PhantomData` markers
+    let mut fields = data.fields.iter().filter(|f| {
+        !is_phantom_data(&f.ty)
+            && !["skip", "skip_serializing", "skip_deserializing"]
+                .iter()
+                .any(|flag| has_serde_key(&f.attrs, flag))
+    });
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => Some(field),
+        _ => None,
+    }
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    matches!(ty, Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "PhantomData"))
+}
+
+/// Wrap a newtype's schema in `Schema::Branded` so it keeps its own identity.
+fn impl_brand(
+    input: &DeriveInput,
+    schema_impl: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if !is_newtype(input) {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "SchemaBridge: #[schema_bridge(brand)] is only supported on newtype structs",
+        ));
     }
+
+    let brand = input.ident.to_string();
+    Ok(quote! {
+        ::schema_bridge::Schema::Branded {
+            name: #brand.to_string(),
+            schema: Box::new({ #schema_impl }),
+        }
+    })
+}
+
+/// Override `ts_declarations()` for enums exported as a TS `enum` and/or a
+/// runtime array of their values.
+fn impl_ts_declarations(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return match container.ts_enum.or(container.ts_const_values) {
+            Some(span) => Err(syn::Error::new(
+                span,
+                "SchemaBridge: `ts_enum` and `ts_const_values` are only supported on enums",
+            )),
+            None => Ok(quote! {}),
+        };
+    };
+    if container.ts_enum.is_none() && container.ts_const_values.is_none() {
+        return Ok(quote! {});
+    }
+
+    let primary = if container.ts_enum.is_some() {
+        let members = data.variants.iter().map(|v| v.ident.to_string());
+        quote! {
+            ::schema_bridge::Declaration::enumeration(
+                name,
+                <Self as ::schema_bridge::SchemaBridge>::to_schema(),
+                vec![#(#members.to_string()),*],
+            )
+        }
+    } else {
+        quote! {
+            ::schema_bridge::Declaration::new(
+                name,
+                <Self as ::schema_bridge::SchemaBridge>::to_schema(),
+            )
+        }
+    };
+
+    let const_values = if container.ts_const_values.is_some() {
+        quote! {
+            declarations.push(::schema_bridge::Declaration::const_values(
+                name,
+                <Self as ::schema_bridge::SchemaBridge>::to_schema(),
+            ));
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        fn ts_declarations(name: &str) -> Vec<::schema_bridge::Declaration> {
+            let mut declarations = vec![#primary];
+            #const_values
+            declarations
+        }
+    })
 }
 
 /// Extract rename_all from #[serde(rename_all = "...")]
 fn get_serde_rename_all(attrs: &[syn::Attribute]) -> Option<String> {
+    get_serde_value(attrs, "rename_all")
+}
+
+/// Extract the string value of `key` from #[serde(..., key = "...", ...)]
+fn get_serde_value(attrs: &[syn::Attribute], key: &str) -> Option<String> {
+    let mut found = None;
     for attr in attrs {
-        if attr.path().is_ident("serde") {
-            if let Meta::List(meta_list) = &attr.meta {
-                // Parse the meta list
-                let nested: Result<Meta, _> = syn::parse2(meta_list.tokens.clone());
-                if let Ok(Meta::NameValue(nv)) = nested {
-                    if nv.path.is_ident("rename_all") {
-                        if let syn::Expr::Lit(expr_lit) = &nv.value {
-                            if let Lit::Str(lit_str) = &expr_lit.lit {
-                                return Some(lit_str.value());
-                            }
-                        }
-                    }
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        // serde validates its own attributes, so anything unparsable is ignored here
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) && meta.input.peek(Token![=]) {
+                if let Lit::Str(s) = meta.value()?.parse()? {
+                    found = Some(s.value());
                 }
+                return Ok(());
             }
+            skip_meta_value(&meta)
+        });
+    }
+    found
+}
+
+/// Check whether #[serde(...)] mentions `key`, as a flag or with a value
+fn has_serde_key(attrs: &[syn::Attribute], key: &str) -> bool {
+    let mut found = false;
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
         }
+        let _ = attr.parse_nested_meta(|meta| {
+            found |= meta.path.is_ident(key);
+            skip_meta_value(&meta)
+        });
     }
-    None
+    found
+}
+
+/// Field names from #[serde(rename = "...")] or
+/// #[serde(rename(serialize = "...", deserialize = "..."))], as
+/// (serialize, deserialize)
+fn get_serde_rename(attrs: &[syn::Attribute]) -> (Option<String>, Option<String>) {
+    let mut names = (None, None);
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("rename") {
+                return skip_meta_value(&meta);
+            }
+            if meta.input.peek(Token![=]) {
+                if let Lit::Str(s) = meta.value()?.parse()? {
+                    names = (Some(s.value()), Some(s.value()));
+                }
+                return Ok(());
+            }
+            meta.parse_nested_meta(|side| {
+                let Lit::Str(s) = side.value()?.parse()? else {
+                    return Ok(());
+                };
+                if side.path.is_ident("serialize") {
+                    names.0 = Some(s.value());
+                } else if side.path.is_ident("deserialize") {
+                    names.1 = Some(s.value());
+                }
+                Ok(())
+            })
+        });
+    }
+    names
+}
+
+/// Consume `= value` or `(...)` after a key we don't care about.
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let _content;
+        syn::parenthesized!(_content in meta.input);
+    }
+    Ok(())
+}
+
+/// The type serialized through by #[serde(into = "...")], or deserialized
+/// through by `from` / `try_from`.
+fn serde_proxy(attrs: &[syn::Attribute], mode: Mode) -> syn::Result<Option<Type>> {
+    let keys: &[&str] = match mode {
+        Mode::Output => &["into"],
+        Mode::Input => &["from", "try_from"],
+    };
+    for &key in keys {
+        if let Some(ty) = get_serde_value(attrs, key) {
+            return syn::parse_str(&ty).map(Some).map_err(|err| {
+                syn::Error::new(
+                    err.span(),
+                    format!("SchemaBridge: cannot parse `{key}` type `{ty}`"),
+                )
+            });
+        }
+    }
+    Ok(None)
 }
 
 /// Detect if a name is in snake_case format
@@ -297,90 +846,570 @@ fn pascal_to_kebab(name: &str) -> String {
 
 /// Parse #[schema(...)] attributes on a field.
 ///
-/// Supported: required, min = N, max = N, min_len = N, max_len = N, one_of("a", "b", ...)
+/// Supported: required, min = N, max = N, min_len = N, max_len = N, one_of("a", "b", ...),
+/// pattern = "regex", optional_style = "optional" | "optional_nullable" | "nullable",
+/// untagged_result (a `Result` exported as `T | E`), bytes = "base64" | "hex" | "array",
+/// items(...) and keys(...) holding nested constraints for collection elements and map keys,
+/// and the overrides rename = "name", skip, ts_type = "TsType", schema = path::to::fn
 #[derive(Default)]
 struct SchemaFieldAttrs {
     required: Option<bool>,
+    /// Exported name, winning over serde's `rename_all`
+    rename: Option<String>,
+    skip: bool,
+    /// Hand-written TypeScript for the field type
+    ts_type: Option<String>,
+    /// `fn() -> Schema` used instead of the field type's `to_schema()`
+    schema_fn: Option<syn::Path>,
+    /// Wire encoding of a byte buffer, given or inferred from `#[serde(with)]`
+    bytes: Option<String>,
+    optional_style: Option<String>,
+    untagged_result: bool,
     min: Option<f64>,
     max: Option<f64>,
     min_len: Option<usize>,
     max_len: Option<usize>,
     one_of: Option<Vec<String>>,
+    pattern: Option<String>,
+    /// Constraints on each element of a sequence or value of a map
+    items: Option<Box<SchemaFieldAttrs>>,
+    /// Constraints on each key of a map
+    keys: Option<Box<SchemaFieldAttrs>>,
+    /// Each key that was given, for diagnostics
+    spans: Vec<(&'static str, Span)>,
+}
+
+impl SchemaFieldAttrs {
+    fn has_constraints(&self) -> bool {
+        self.min.is_some()
+            || self.max.is_some()
+            || self.min_len.is_some()
+            || self.max_len.is_some()
+            || self.one_of.is_some()
+            || self.pattern.is_some()
+    }
+
+    /// Fill values not set on `self` from `fallback`.
+    fn or(self, fallback: SchemaFieldAttrs) -> SchemaFieldAttrs {
+        SchemaFieldAttrs {
+            required: self.required.or(fallback.required),
+            rename: self.rename.or(fallback.rename),
+            skip: self.skip || fallback.skip,
+            ts_type: self.ts_type.or(fallback.ts_type),
+            schema_fn: self.schema_fn.or(fallback.schema_fn),
+            bytes: self.bytes.or(fallback.bytes),
+            optional_style: self.optional_style.or(fallback.optional_style),
+            untagged_result: self.untagged_result || fallback.untagged_result,
+            min: self.min.or(fallback.min),
+            max: self.max.or(fallback.max),
+            min_len: self.min_len.or(fallback.min_len),
+            max_len: self.max_len.or(fallback.max_len),
+            one_of: self.one_of.or(fallback.one_of),
+            pattern: self.pattern.or(fallback.pattern),
+            items: self.items.or(fallback.items),
+            keys: self.keys.or(fallback.keys),
+            spans: self.spans.into_iter().chain(fallback.spans).collect(),
+        }
+    }
+}
+
+/// Build a `::schema_bridge::Constraints` literal.
+fn constraints_expr(attrs: &SchemaFieldAttrs) -> proc_macro2::TokenStream {
+    let min = attrs.min.map(|v| quote! { .min(#v) });
+    let max = attrs.max.map(|v| quote! { .max(#v) });
+    let min_len = attrs.min_len.map(|v| quote! { .min_len(#v) });
+    let max_len = attrs.max_len.map(|v| quote! { .max_len(#v) });
+    let one_of = attrs
+        .one_of
+        .as_ref()
+        .map(|vals| quote! { .one_of([#(#vals),*]) });
+    let pattern = attrs.pattern.as_ref().map(|p| quote! { .pattern(#p) });
+
+    quote! {
+        ::schema_bridge::Constraints::default()
+            #min #max #min_len #max_len #one_of #pattern
+    }
+}
+
+/// Push `items(...)` / `keys(...)` constraints down into the element schemas.
+fn constrain_elements(
+    schema: proc_macro2::TokenStream,
+    attrs: &SchemaFieldAttrs,
+) -> proc_macro2::TokenStream {
+    let mut schema = schema;
+    if let Some(items) = &attrs.items {
+        let item = constrain_elements(constrain(quote! { item }, items), items);
+        schema = quote! { ({ #schema }).map_items(&|item| #item) };
+    }
+    if let Some(keys) = &attrs.keys {
+        let key = constrain_elements(constrain(quote! { key }, keys), keys);
+        schema = quote! { ({ #schema }).map_keys(&|key| #key) };
+    }
+    schema
+}
+
+/// Wrap `schema` in `Schema::Constrained` when `attrs` carries any constraint.
+fn constrain(
+    schema: proc_macro2::TokenStream,
+    attrs: &SchemaFieldAttrs,
+) -> proc_macro2::TokenStream {
+    if !attrs.has_constraints() {
+        return schema;
+    }
+    let constraints = constraints_expr(attrs);
+    quote! {
+        ::schema_bridge::Schema::Constrained {
+            schema: Box::new({ #schema }),
+            constraints: #constraints,
+        }
+    }
 }
 
-fn parse_schema_attrs(attrs: &[syn::Attribute]) -> SchemaFieldAttrs {
+fn parse_schema_attrs(attrs: &[syn::Attribute]) -> syn::Result<SchemaFieldAttrs> {
     let mut result = SchemaFieldAttrs::default();
 
     for attr in attrs {
         if !attr.path().is_ident("schema") {
             continue;
         }
-        let _ = attr.parse_nested_meta(|meta| {
-            if meta.path.is_ident("required") {
-                result.required = Some(true);
-                return Ok(());
+        attr.parse_nested_meta(|meta| parse_schema_meta(meta, &mut result))?;
+    }
+
+    Ok(result)
+}
+
+/// Keys that override how a single field is exported.
+const OVERRIDE_KEYS: &[&str] = &["rename", "skip", "ts_type", "schema", "bytes"];
+
+/// Keys describing a field as a whole, meaningless for its elements.
+const FIELD_ONLY_KEYS: &[&str] = &[
+    "required",
+    "optional_style",
+    "untagged_result",
+    "rename",
+    "skip",
+    "ts_type",
+    "schema",
+    "bytes",
+];
+
+const SCHEMA_KEYS: &[&str] = &[
+    "required",
+    "optional_style",
+    "untagged_result",
+    "min",
+    "max",
+    "min_len",
+    "max_len",
+    "one_of",
+    "pattern",
+    "items",
+    "keys",
+    "rename",
+    "skip",
+    "ts_type",
+    "schema",
+    "bytes",
+];
+
+fn parse_schema_meta(
+    meta: syn::meta::ParseNestedMeta,
+    result: &mut SchemaFieldAttrs,
+) -> syn::Result<()> {
+    let Some(key) = SCHEMA_KEYS.iter().find(|k| meta.path.is_ident(k)) else {
+        return Err(unknown_key(&meta, "schema", &SCHEMA_KEYS.join(", ")));
+    };
+    result.spans.push((key, meta.path.span()));
+
+    match *key {
+        "required" => result.required = Some(true),
+        "untagged_result" => result.untagged_result = true,
+        "optional_style" => {
+            let s = parse_str_lit(&meta, key)?;
+            if !["optional", "optional_nullable", "nullable"].contains(&s.value().as_str()) {
+                return Err(syn::Error::new_spanned(
+                    s,
+                    "SchemaBridge: `optional_style` must be \"optional\", \"optional_nullable\" or \"nullable\"",
+                ));
             }
-            if meta.path.is_ident("min") {
-                let value = meta.value()?;
-                let lit: Lit = value.parse()?;
-                if let Lit::Float(f) = &lit {
-                    result.min = Some(f.base10_parse::<f64>()?);
-                } else if let Lit::Int(i) = &lit {
-                    result.min = Some(i.base10_parse::<f64>()?);
-                }
-                return Ok(());
+            result.optional_style = Some(s.value());
+        }
+        "min" | "max" => {
+            let lit: Lit = meta.value()?.parse()?;
+            let value = match &lit {
+                Lit::Float(f) => f.base10_parse::<f64>()?,
+                Lit::Int(i) => i.base10_parse::<f64>()?,
+                _ => return Err(expected_lit(&lit, key, "a number")),
+            };
+            if *key == "min" {
+                result.min = Some(value);
+            } else {
+                result.max = Some(value);
             }
-            if meta.path.is_ident("max") {
-                let value = meta.value()?;
-                let lit: Lit = value.parse()?;
-                if let Lit::Float(f) = &lit {
-                    result.max = Some(f.base10_parse::<f64>()?);
-                } else if let Lit::Int(i) = &lit {
-                    result.max = Some(i.base10_parse::<f64>()?);
-                }
-                return Ok(());
+            check_range(result.min, result.max, lit.span(), "min", "max")?;
+        }
+        "min_len" | "max_len" => {
+            let lit: Lit = meta.value()?.parse()?;
+            let Lit::Int(i) = &lit else {
+                return Err(expected_lit(&lit, key, "an integer"));
+            };
+            let value = i.base10_parse::<usize>()?;
+            if *key == "min_len" {
+                result.min_len = Some(value);
+            } else {
+                result.max_len = Some(value);
             }
-            if meta.path.is_ident("min_len") {
-                let value = meta.value()?;
-                let lit: Lit = value.parse()?;
-                if let Lit::Int(i) = &lit {
-                    result.min_len = Some(i.base10_parse::<usize>()?);
+            check_range(
+                result.min_len,
+                result.max_len,
+                lit.span(),
+                "min_len",
+                "max_len",
+            )?;
+        }
+        "one_of" => {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let lits: Punctuated<Lit, Token![,]> =
+                content.parse_terminated(Lit::parse, Token![,])?;
+            let mut values = Vec::with_capacity(lits.len());
+            for lit in &lits {
+                match lit {
+                    Lit::Str(s) => values.push(s.value()),
+                    _ => return Err(expected_lit(lit, key, "string literals")),
                 }
-                return Ok(());
             }
-            if meta.path.is_ident("max_len") {
-                let value = meta.value()?;
-                let lit: Lit = value.parse()?;
-                if let Lit::Int(i) = &lit {
-                    result.max_len = Some(i.base10_parse::<usize>()?);
+            if values.is_empty() {
+                return Err(meta.error("SchemaBridge: `one_of` needs at least one value"));
+            }
+            result.one_of = Some(values);
+        }
+        "pattern" => {
+            let s = parse_str_lit(&meta, key)?;
+            // The same parser `CompiledSchema` uses, so a bad pattern fails
+            // here rather than when the schema is first validated against
+            if let Err(e) = regex_syntax::Parser::new().parse(&s.value()) {
+                let reason = match e {
+                    regex_syntax::Error::Parse(e) => e.kind().to_string(),
+                    regex_syntax::Error::Translate(e) => e.kind().to_string(),
+                    e => e.to_string(),
+                };
+                return Err(syn::Error::new_spanned(
+                    s,
+                    format!("SchemaBridge: invalid `pattern`: {reason}"),
+                ));
+            }
+            result.pattern = Some(s.value());
+        }
+        "rename" => result.rename = Some(parse_str_lit(&meta, key)?.value()),
+        "skip" => result.skip = true,
+        "ts_type" => result.ts_type = Some(parse_str_lit(&meta, key)?.value()),
+        "schema" => result.schema_fn = Some(meta.value()?.parse()?),
+        "bytes" => {
+            let s = parse_str_lit(&meta, key)?;
+            if bytes_encoding(&s.value()).is_none() {
+                return Err(syn::Error::new_spanned(
+                    s,
+                    "SchemaBridge: `bytes` must be \"base64\", \"hex\" or \"array\"",
+                ));
+            }
+            result.bytes = Some(s.value());
+        }
+        "items" => {
+            let items = result.items.get_or_insert_with(Default::default);
+            meta.parse_nested_meta(|nested| parse_schema_meta(nested, items))?;
+            reject_keys(items, FIELD_ONLY_KEYS, "`items(...)`")?;
+        }
+        "keys" => {
+            let keys = result.keys.get_or_insert_with(Default::default);
+            meta.parse_nested_meta(|nested| parse_schema_meta(nested, keys))?;
+            reject_keys(keys, FIELD_ONLY_KEYS, "`keys(...)`")?;
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+fn parse_str_lit(meta: &syn::meta::ParseNestedMeta, key: &str) -> syn::Result<syn::LitStr> {
+    match meta.value()?.parse()? {
+        Lit::Str(s) => Ok(s),
+        lit => Err(expected_lit(&lit, key, "a string")),
+    }
+}
+
+fn expected_lit(lit: &Lit, key: &str, expected: &str) -> syn::Error {
+    syn::Error::new_spanned(lit, format!("SchemaBridge: `{key}` expects {expected}"))
+}
+
+/// Reject a lower bound above its upper bound, pointing at the later of the two.
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    lo: Option<T>,
+    hi: Option<T>,
+    span: Span,
+    lo_key: &str,
+    hi_key: &str,
+) -> syn::Result<()> {
+    match (lo, hi) {
+        (Some(lo), Some(hi)) if lo > hi => Err(syn::Error::new(
+            span,
+            format!("SchemaBridge: `{lo_key}` ({lo}) is greater than `{hi_key}` ({hi})"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Coarse shape of a field type, judged from its syntax alone.
+#[derive(Clone, Copy, PartialEq)]
+enum TypeShape {
+    Number,
+    Bool,
+    String,
+    Sequence,
+    Map,
+    Result,
+    /// User types, generics and anything else we can't see through
+    Unknown,
+}
+
+impl TypeShape {
+    fn of(ty: &Type) -> TypeShape {
+        match strip_wrappers(ty) {
+            Type::Array(_) | Type::Slice(_) => TypeShape::Sequence,
+            Type::Path(p) if p.qself.is_none() => {
+                let Some(segment) = p.path.segments.last() else {
+                    return TypeShape::Unknown;
+                };
+                match segment.ident.to_string().as_str() {
+                    "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32"
+                    | "i64" | "i128" | "isize" | "f32" | "f64" => TypeShape::Number,
+                    "bool" => TypeShape::Bool,
+                    "String" | "str" | "char" => TypeShape::String,
+                    "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "BinaryHeap"
+                    | "IndexSet" | "SmallVec" | "ArrayVec" => TypeShape::Sequence,
+                    "HashMap" | "BTreeMap" | "IndexMap" => TypeShape::Map,
+                    "Result" => TypeShape::Result,
+                    _ => TypeShape::Unknown,
                 }
-                return Ok(());
             }
-            if meta.path.is_ident("one_of") {
-                let content;
-                syn::parenthesized!(content in meta.input);
-                let lits: Punctuated<Lit, Token![,]> =
-                    content.parse_terminated(Lit::parse, Token![,])?;
-                let values: Vec<String> = lits
-                    .into_iter()
-                    .filter_map(|lit| {
-                        if let Lit::Str(s) = lit {
-                            Some(s.value())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                if !values.is_empty() {
-                    result.one_of = Some(values);
+            _ => TypeShape::Unknown,
+        }
+    }
+
+    /// Whether the `#[schema]` key `key` makes sense on this shape.
+    fn accepts(self, key: &str) -> bool {
+        use TypeShape::*;
+        match key {
+            "min" | "max" => matches!(self, Number | Unknown),
+            "min_len" | "max_len" => matches!(self, String | Sequence | Map | Unknown),
+            "pattern" | "one_of" => matches!(self, String | Unknown),
+            "items" => matches!(self, Sequence | Map | Unknown),
+            "keys" => matches!(self, Map | Unknown),
+            "untagged_result" => matches!(self, Result | Sequence | Map | Unknown),
+            "bytes" => matches!(self, Sequence | Unknown),
+            _ => true,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            TypeShape::Number => "numeric",
+            TypeShape::Bool => "boolean",
+            TypeShape::String => "string",
+            TypeShape::Sequence => "collection",
+            TypeShape::Map => "map",
+            TypeShape::Result => "`Result`",
+            TypeShape::Unknown => "other",
+        }
+    }
+}
+
+/// Look through references, `Option` and smart pointers to the value type.
+fn strip_wrappers(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(r) => strip_wrappers(&r.elem),
+        Type::Paren(p) => strip_wrappers(&p.elem),
+        Type::Group(g) => strip_wrappers(&g.elem),
+        Type::Path(p) => match p.path.segments.last() {
+            Some(segment)
+                if ["Option", "Box", "Rc", "Arc"].contains(&&*segment.ident.to_string()) =>
+            {
+                match type_args(ty).first() {
+                    Some(inner) => strip_wrappers(inner),
+                    None => ty,
                 }
-                return Ok(());
             }
-            Err(meta.error("unknown schema attribute"))
-        });
+            _ => ty,
+        },
+        _ => ty,
     }
+}
 
-    result
+fn last_ident_is(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == name))
+}
+
+/// Generic type arguments of the last path segment.
+fn type_args(ty: &Type) -> Vec<&Type> {
+    let Type::Path(p) = ty else {
+        return Vec::new();
+    };
+    let Some(syn::PathArguments::AngleBracketed(args)) =
+        p.path.segments.last().map(|s| &s.arguments)
+    else {
+        return Vec::new();
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Reject constraints that can't apply to `ty`, such as `min_len` on a number
+/// or `one_of` on a `Vec`. Types we can't classify are left alone.
+fn check_applicable(attrs: &SchemaFieldAttrs, ty: &Type) -> syn::Result<()> {
+    let ty = strip_wrappers(ty);
+    let shape = TypeShape::of(ty);
+    for (key, span) in &attrs.spans {
+        if !shape.accepts(key) {
+            let usage = if matches!(*key, "items" | "keys") {
+                format!("{key}(...)")
+            } else {
+                key.to_string()
+            };
+            return Err(syn::Error::new(
+                *span,
+                format!(
+                    "SchemaBridge: `{usage}` cannot be used on {} fields",
+                    shape.describe()
+                ),
+            ));
+        }
+    }
+
+    let args = type_args(ty);
+    let (item, key) = match (shape, ty) {
+        (TypeShape::Sequence, Type::Array(a)) => (Some(&*a.elem), None),
+        (TypeShape::Sequence, Type::Slice(s)) => (Some(&*s.elem), None),
+        // `SmallVec<[T; N]>` names its element inside the backing array
+        (TypeShape::Sequence, _) => match args.first() {
+            Some(Type::Array(a)) if last_ident_is(ty, "SmallVec") => (Some(&*a.elem), None),
+            first => (first.copied(), None),
+        },
+        (TypeShape::Map, _) => (args.get(1).copied(), args.first().copied()),
+        _ => (None, None),
+    };
+    if let (Some(items), Some(item)) = (&attrs.items, item) {
+        check_applicable(items, item)?;
+    }
+    if let (Some(keys), Some(key)) = (&attrs.keys, key) {
+        check_applicable(keys, key)?;
+    }
+    Ok(())
+}
+
+/// Plural description of a non-newtype container, for error messages.
+fn container_kind(input: &DeriveInput) -> &'static str {
+    match &input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(_) => "structs with named fields",
+            Fields::Unnamed(_) => "tuple structs",
+            Fields::Unit => "unit structs",
+        },
+        Data::Enum(_) => "enums",
+        Data::Union(_) => "unions",
+    }
+}
+
+/// Reject keys that only make sense on named struct fields.
+fn reject_keys(attrs: &SchemaFieldAttrs, keys: &[&str], position: &str) -> syn::Result<()> {
+    match attrs.spans.iter().find(|(key, _)| keys.contains(key)) {
+        Some((key, span)) => Err(syn::Error::new(
+            *span,
+            format!("SchemaBridge: `{key}` is not supported on {position}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Schema of a field of type `ty`, honoring `ts_type` / `schema` overrides.
+///
+/// With an override the field type needs no `SchemaBridge` impl.
+fn field_schema_expr(ty: &Type, attrs: &SchemaFieldAttrs, mode: Mode) -> proc_macro2::TokenStream {
+    let method = mode.shared_method();
+    let bytes = attrs.bytes.as_deref().and_then(bytes_encoding);
+    let schema = match (&attrs.schema_fn, bytes, &attrs.ts_type) {
+        (Some(path), _, _) => quote! { #path() },
+        (None, Some(encoding), _) => quote! { ::schema_bridge::Schema::Bytes(#encoding) },
+        (None, None, Some(_)) => quote! { ::schema_bridge::Schema::Any },
+        (None, None, None) => quote! { <#ty as ::schema_bridge::SchemaBridge>::#method() },
+    };
+    let schema = if attrs.untagged_result {
+        quote! { #schema.untagged_result() }
+    } else {
+        schema
+    };
+    match &attrs.ts_type {
+        Some(ts) => quote! {
+            ::schema_bridge::Schema::Custom {
+                ts: #ts.to_string(),
+                schema: Box::new(#schema),
+            }
+        },
+        None => schema,
+    }
+}
+
+fn bytes_encoding(name: &str) -> Option<proc_macro2::TokenStream> {
+    let variant = match name {
+        "base64" => quote! { Base64 },
+        "hex" => quote! { Hex },
+        "array" => quote! { Array },
+        _ => return None,
+    };
+    Some(quote! { ::schema_bridge::BytesEncoding::#variant })
+}
+
+/// Byte encoding implied by the serde attributes or type of a byte-typed
+/// field: `#[serde(with = "serde_bytes")]`, a helper module path with a
+/// `base64` or `hex` segment, or `serde_bytes::ByteBuf` / `Bytes`.
+fn inferred_bytes(f: &syn::Field) -> Option<String> {
+    let ty = strip_wrappers(&f.ty);
+    if !is_byte_type(ty) {
+        return None;
+    }
+    let with = ["with", "serialize_with", "deserialize_with"]
+        .iter()
+        .find_map(|key| get_serde_value(&f.attrs, key));
+    let has_segment = |name: &str| {
+        with.as_deref()
+            .is_some_and(|path| path.split("::").any(|segment| segment.trim() == name))
+    };
+    let encoding = if has_segment("base64") {
+        "base64"
+    } else if has_segment("hex") {
+        "hex"
+    } else if has_segment("serde_bytes")
+        || (with.is_none() && (last_ident_is(ty, "ByteBuf") || last_ident_is(ty, "Bytes")))
+    {
+        "array"
+    } else {
+        return None;
+    };
+    Some(encoding.to_string())
+}
+
+/// `Vec<u8>`, `[u8; N]`, `[u8]`, `ByteBuf` or `Bytes`.
+fn is_byte_type(ty: &Type) -> bool {
+    let is_u8 = |ty: &Type| matches!(ty, Type::Path(p) if p.path.is_ident("u8"));
+    match ty {
+        Type::Array(a) => is_u8(&a.elem),
+        Type::Slice(s) => is_u8(&s.elem),
+        _ if last_ident_is(ty, "Vec") => type_args(ty).first().is_some_and(|t| is_u8(t)),
+        _ => last_ident_is(ty, "ByteBuf") || last_ident_is(ty, "Bytes"),
+    }
 }
 
 /// Check if a type is Option<T> and return the inner type T
@@ -398,83 +1427,179 @@ fn extract_option_inner(ty: &Type) -> Option<&Type> {
     None
 }
 
-fn impl_to_schema(_name: &Ident, input: &DeriveInput) -> proc_macro2::TokenStream {
-    match &input.data {
+/// A named struct field as described by `to_schema()` and `to_schema_input()`.
+struct NamedField<'a> {
+    output: FieldSide,
+    input: FieldSide,
+    /// Type describing the field value (`T` for `Option<T>` fields)
+    ty: &'a Type,
+    nullable: bool,
+    attrs: SchemaFieldAttrs,
+}
+
+/// How a field appears on one side of serde.
+struct FieldSide {
+    /// Name after renames
+    name: String,
+    required: bool,
+    /// `false` for fields serde skips on this side
+    present: bool,
+}
+
+impl NamedField<'_> {
+    fn side(&self, mode: Mode) -> &FieldSide {
+        match mode {
+            Mode::Output => &self.output,
+            Mode::Input => &self.input,
+        }
+    }
+}
+
+fn analyze_named_field<'a>(
+    f: &'a syn::Field,
+    rename_all: Option<&str>,
+    container_default: bool,
+) -> syn::Result<NamedField<'a>> {
+    let field_str = f.ident.as_ref().unwrap().to_string();
+    let mut attrs = parse_schema_attrs(&f.attrs)?;
+    check_applicable(&attrs, &f.ty)?;
+    if attrs.bytes.is_none() {
+        attrs.bytes = inferred_bytes(f);
+    }
+
+    // #[schema(rename)] wins over serde's own renames
+    let (serialize_name, deserialize_name) = get_serde_rename(&f.attrs);
+    let name = |serde_name: Option<String>| match (&attrs.rename, serde_name, rename_all) {
+        (Some(rename), _, _) => rename.clone(),
+        (None, Some(serde_name), _) => serde_name,
+        (None, None, Some(rule)) => apply_rename_rule(&field_str, rule),
+        (None, None, None) => field_str.clone(),
+    };
+
+    // Option<T> fields: schema is T, nullable, and optional unless #[schema(required)]
+    let (ty, is_option) = match extract_option_inner(&f.ty) {
+        Some(inner) => (inner, true),
+        None => (&f.ty, false),
+    };
+    // Option<Option<T>> fields tell "absent" from `null`: `field?: T | null`
+    let ty = match extract_option_inner(ty) {
+        Some(inner) => {
+            attrs
+                .optional_style
+                .get_or_insert_with(|| "optional_nullable".to_string());
+            inner
+        }
+        None => ty,
+    };
+    let skip = has_serde_key(&f.attrs, "skip");
+    let output = FieldSide {
+        name: name(serialize_name),
+        required: attrs
+            .required
+            .unwrap_or(!is_option && !has_serde_key(&f.attrs, "skip_serializing_if")),
+        present: !skip && !has_serde_key(&f.attrs, "skip_serializing"),
+    };
+    let input = FieldSide {
+        name: name(deserialize_name),
+        required: attrs
+            .required
+            .unwrap_or(!is_option && !container_default && !has_serde_key(&f.attrs, "default")),
+        present: !skip && !has_serde_key(&f.attrs, "skip_deserializing"),
+    };
+
+    Ok(NamedField {
+        output,
+        input,
+        ty,
+        nullable: is_option,
+        attrs,
+    })
+}
+
+/// `#[schema(...)]` of an element of a multi-field tuple struct, which
+/// describes its schema but has no name or presence of its own.
+fn analyze_tuple_field(f: &syn::Field) -> syn::Result<SchemaFieldAttrs> {
+    let mut attrs = parse_schema_attrs(&f.attrs)?;
+    reject_keys(
+        &attrs,
+        &["required", "optional_style", "rename", "skip"],
+        "tuple struct fields",
+    )?;
+    check_applicable(&attrs, &f.ty)?;
+    if attrs.bytes.is_none() {
+        attrs.bytes = inferred_bytes(f);
+    }
+    Ok(attrs)
+}
+
+fn impl_to_schema(
+    _name: &Ident,
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    mode: Mode,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some(inner) = newtype_field(input) {
+        return impl_newtype(input, inner, mode);
+    }
+
+    let schema = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => {
                 let rename_all = get_serde_rename_all(&input.attrs);
+                let container_default = has_serde_key(&input.attrs, "default");
 
-                let field_exprs = fields.named.iter().map(|f| {
-                    let field_ident = f.ident.as_ref().unwrap();
-                    let field_str = field_ident.to_string();
-                    let ty = &f.ty;
-                    let schema_attrs = parse_schema_attrs(&f.attrs);
-
-                    let field_name = if let Some(ref rule) = rename_all {
-                        apply_rename_rule(&field_str, rule)
-                    } else {
-                        field_str
-                    };
-
-                    // Determine if Option<T> and extract inner type
-                    let (schema_expr, is_option) = if let Some(inner) = extract_option_inner(ty) {
-                        (
-                            quote! { <#inner as ::schema_bridge::SchemaBridge>::to_schema() },
-                            true,
-                        )
-                    } else {
-                        (
-                            quote! { <#ty as ::schema_bridge::SchemaBridge>::to_schema() },
-                            false,
-                        )
-                    };
-
-                    // Required: explicit #[schema(required)] > Option detection > default true
-                    let required = match schema_attrs.required {
-                        Some(r) => r,
-                        None => !is_option,
-                    };
-
-                    // Build constraints
-                    let min_expr = match schema_attrs.min {
-                        Some(v) => quote! { Some(#v) },
-                        None => quote! { None },
-                    };
-                    let max_expr = match schema_attrs.max {
-                        Some(v) => quote! { Some(#v) },
-                        None => quote! { None },
-                    };
-                    let min_len_expr = match schema_attrs.min_len {
-                        Some(v) => quote! { Some(#v) },
-                        None => quote! { None },
-                    };
-                    let max_len_expr = match schema_attrs.max_len {
-                        Some(v) => quote! { Some(#v) },
-                        None => quote! { None },
-                    };
-                    let one_of_expr = match &schema_attrs.one_of {
-                        Some(vals) => {
-                            let lit_vals = vals.iter().map(|s| quote! { #s.to_string() });
-                            quote! { Some(vec![#(#lit_vals),*]) }
-                        }
-                        None => quote! { None },
-                    };
-
-                    quote! {
-                        ::schema_bridge::Field {
-                            name: #field_name.to_string(),
-                            schema: #schema_expr,
-                            required: #required,
-                            constraints: ::schema_bridge::Constraints {
-                                min: #min_expr,
-                                max: #max_expr,
-                                min_len: #min_len_expr,
-                                max_len: #max_len_expr,
-                                one_of: #one_of_expr,
-                            },
-                        }
+                // Report every bad field at once rather than one per build
+                let mut errors: Option<syn::Error> = None;
+                let mut named = Vec::with_capacity(fields.named.len());
+                for f in &fields.named {
+                    match analyze_named_field(f, rename_all.as_deref(), container_default) {
+                        Ok(field) => named.push(field),
+                        Err(err) => match &mut errors {
+                            Some(errors) => errors.combine(err),
+                            None => errors = Some(err),
+                        },
                     }
-                });
+                }
+                if let Some(errors) = errors {
+                    return Err(errors);
+                }
+
+                let field_exprs = named
+                    .iter()
+                    .filter(|field| !field.attrs.skip && field.side(mode).present)
+                    .map(|field| {
+                        let field_name = &field.side(mode).name;
+                        let schema_expr = constrain_elements(
+                            field_schema_expr(field.ty, &field.attrs, mode),
+                            &field.attrs,
+                        );
+                        let required = field.side(mode).required;
+                        let nullable = field.nullable;
+                        let schema_attrs = &field.attrs;
+
+                        let optional_style_expr = match schema_attrs.optional_style.as_deref() {
+                            Some("optional") => {
+                                quote! { .optional_style(::schema_bridge::OptionalStyle::Optional) }
+                            }
+                            Some("optional_nullable") => {
+                                quote! { .optional_style(::schema_bridge::OptionalStyle::OptionalNullable) }
+                            }
+                            Some("nullable") => {
+                                quote! { .optional_style(::schema_bridge::OptionalStyle::Nullable) }
+                            }
+                            _ => quote! {},
+                        };
+
+                        let constraints_expr = constraints_expr(schema_attrs);
+
+                        quote! {
+                            ::schema_bridge::Field::new(#field_name, #schema_expr)
+                                .required(#required)
+                                .nullable(#nullable)
+                                #optional_style_expr
+                                .constraints(#constraints_expr)
+                        }
+                    });
 
                 quote! {
                     ::schema_bridge::Schema::Object(vec![
@@ -483,24 +1608,58 @@ fn impl_to_schema(_name: &Ident, input: &DeriveInput) -> proc_macro2::TokenStrea
                 }
             }
             Fields::Unnamed(fields) => {
-                if fields.unnamed.len() == 1 {
-                    let inner_ty = &fields.unnamed[0].ty;
-                    quote! {
-                        <#inner_ty as ::schema_bridge::SchemaBridge>::to_schema()
-                    }
-                } else {
-                    let types = fields.unnamed.iter().map(|f| {
-                        let ty = &f.ty;
-                        quote! { <#ty as ::schema_bridge::SchemaBridge>::to_schema() }
-                    });
-                    quote! {
-                        ::schema_bridge::Schema::Tuple(vec![#(#types),*])
+                // serde leaves skipped elements out of the sequence entirely
+                let side_skip = match mode {
+                    Mode::Output => "skip_serializing",
+                    Mode::Input => "skip_deserializing",
+                };
+
+                let mut errors: Option<syn::Error> = None;
+                let mut types = Vec::with_capacity(fields.unnamed.len());
+                for f in &fields.unnamed {
+                    match analyze_tuple_field(f) {
+                        Ok(attrs) => {
+                            if has_serde_key(&f.attrs, "skip") || has_serde_key(&f.attrs, side_skip)
+                            {
+                                continue;
+                            }
+                            let schema = field_schema_expr(&f.ty, &attrs, mode);
+                            types.push(constrain(constrain_elements(schema, &attrs), &attrs));
+                        }
+                        Err(err) => match &mut errors {
+                            Some(errors) => errors.combine(err),
+                            None => errors = Some(err),
+                        },
                     }
                 }
+                if let Some(errors) = errors {
+                    return Err(errors);
+                }
+
+                quote! {
+                    ::schema_bridge::Schema::Tuple(vec![#(#types),*])
+                }
             }
             Fields::Unit => quote! { ::schema_bridge::Schema::Null },
         },
         Data::Enum(data) => {
+            // Variants are exported by name only, so their fields have
+            // nowhere to put a schema
+            for field in data.variants.iter().flat_map(|v| &v.fields) {
+                let attrs = parse_schema_attrs(&field.attrs)?;
+                reject_keys(&attrs, SCHEMA_KEYS, "enum variant fields")?;
+            }
+
+            match numeric_discriminants(container, data) {
+                Some(Ok(values)) => {
+                    return Ok(quote! {
+                        ::schema_bridge::Schema::NumericEnum(vec![#(#values),*])
+                    });
+                }
+                Some(Err(err)) => return Err(err),
+                None => {}
+            }
+
             let rename_all = get_serde_rename_all(&input.attrs);
             let variants = data.variants.iter().map(|v| {
                 let variant_str = v.ident.to_string();
@@ -516,6 +1675,105 @@ fn impl_to_schema(_name: &Ident, input: &DeriveInput) -> proc_macro2::TokenStrea
             }
         }
         _ => quote! { ::schema_bridge::Schema::Any },
+    };
+    Ok(schema)
+}
+
+/// A newtype (or `#[serde(transparent)]` struct) has the schema of its inner field.
+fn impl_newtype(
+    input: &DeriveInput,
+    inner: &syn::Field,
+    mode: Mode,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let inner_ty = &inner.ty;
+    let container_attrs = parse_schema_attrs(&input.attrs)?;
+    reject_keys(&container_attrs, OVERRIDE_KEYS, "types")?;
+    let mut inner_attrs = parse_schema_attrs(&inner.attrs)?;
+    reject_keys(&inner_attrs, &["rename", "skip"], "newtype fields")?;
+    if inner_attrs.bytes.is_none() {
+        inner_attrs.bytes = inferred_bytes(inner);
+    }
+
+    // Constraints on the container win over those on the inner field
+    let attrs = container_attrs.or(inner_attrs);
+    check_applicable(&attrs, inner_ty)?;
+    // Each side was checked alone, but the merge can pair bounds from both
+    let span_of = |keys: &[&str]| {
+        attrs
+            .spans
+            .iter()
+            .find(|(key, _)| keys.contains(key))
+            .map_or_else(Span::call_site, |(_, span)| *span)
+    };
+    check_range(attrs.min, attrs.max, span_of(&["min", "max"]), "min", "max")?;
+    check_range(
+        attrs.min_len,
+        attrs.max_len,
+        span_of(&["min_len", "max_len"]),
+        "min_len",
+        "max_len",
+    )?;
+    let base = field_schema_expr(inner_ty, &attrs, mode);
+    Ok(constrain(constrain_elements(base, &attrs), &attrs))
+}
+
+/// Discriminant values of a `#[schema_bridge(numeric)]` enum, serialized as
+/// numbers (e.g. by `serde_repr`).
+///
+/// The marker is required because a derive can't see which other derives
+/// (such as `Serialize_repr`) share its `#[derive(...)]` list.
+///
+/// Returns `None` for enums serialized by variant name.
+fn numeric_discriminants(
+    container: &ContainerAttrs,
+    data: &syn::DataEnum,
+) -> Option<syn::Result<Vec<i64>>> {
+    container.numeric?;
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return Some(Err(syn::Error::new_spanned(
+            &variant.ident,
+            "SchemaBridge: numeric enums can only have unit variants",
+        )));
+    }
+
+    // Implicit discriminants count up from the previous one, starting at 0
+    let mut next = 0i64;
+    let mut values = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        if let Some((_, expr)) = &variant.discriminant {
+            next = match parse_int_expr(expr) {
+                Some(v) => v,
+                None => {
+                    return Some(Err(syn::Error::new_spanned(
+                        expr,
+                        "SchemaBridge: numeric enum discriminants must be integer literals",
+                    )))
+                }
+            };
+        }
+        values.push(next);
+        next = next.wrapping_add(1);
+    }
+    Some(Ok(values))
+}
+
+/// Evaluate `1`, `-1` or `0x1F` style integer literal expressions.
+fn parse_int_expr(expr: &syn::Expr) -> Option<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(i), ..
+        }) => i.base10_parse::<i64>().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_int_expr(expr).map(|v| -v),
+        syn::Expr::Paren(paren) => parse_int_expr(&paren.expr),
+        _ => None,
     }
 }
 
@@ -0,0 +1,247 @@
+//! Validating a webhook payload: walking the `Schema` tree directly for
+//! every payload, compiling a `CompiledSchema` for every payload, and
+//! reusing one, with deserialization of the same payload for scale; and
+//! validating the deserialized value directly versus through
+//! `serde_json::to_value`.
+//!
+//! Run with `cargo bench -p schema-bridge --features validate --bench validate`.
+
+use divan::{black_box, Bencher};
+use regex::Regex;
+use schema_bridge::{BytesEncoding, CompiledSchema, Constraints, Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Number, Value};
+use std::collections::HashMap;
+
+fn main() {
+    divan::main();
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "snake_case")]
+enum Action {
+    Opened,
+    Closed,
+    Reopened,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Account {
+    #[schema(min = 1)]
+    id: u64,
+    #[schema(min_len = 1, max_len = 39, pattern = "^[A-Za-z0-9-]+$")]
+    login: String,
+    #[schema(pattern = "^[^@]+@[^@]+$")]
+    email: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Label {
+    #[schema(min_len = 1, max_len = 50)]
+    name: String,
+    #[schema(pattern = "^[0-9a-f]{6}$")]
+    color: String,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Issue {
+    #[schema(min = 1)]
+    number: u32,
+    #[schema(min_len = 1, max_len = 256)]
+    title: String,
+    body: Option<String>,
+    author: Account,
+    assignees: Vec<Account>,
+    #[schema(max_len = 100)]
+    labels: Vec<Label>,
+    reactions: HashMap<String, u32>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Webhook {
+    action: Action,
+    issue: Issue,
+    sender: Account,
+    #[schema(min = 0)]
+    delivered_at: i64,
+}
+
+fn account(id: u64) -> Value {
+    json!({ "id": id, "login": format!("user-{id}"), "email": format!("user{id}@example.com") })
+}
+
+fn payload() -> Value {
+    json!({
+        "action": "opened",
+        "issue": {
+            "number": 1347,
+            "title": "Found a bug",
+            "body": "I'm having a problem with this.",
+            "author": account(1),
+            "assignees": (2..6).map(account).collect::<Vec<_>>(),
+            "labels": (0..8)
+                .map(|i| json!({ "name": format!("label-{i}"), "color": "f29513" }))
+                .collect::<Vec<_>>(),
+            "reactions": { "+1": 3, "heart": 1, "rocket": 0 }
+        },
+        "sender": account(1),
+        "delivered_at": 1_700_000_000
+    })
+}
+
+#[divan::bench]
+fn deserialize(bencher: Bencher) {
+    let payload = payload().to_string();
+    bencher.bench(|| serde_json::from_str::<Webhook>(black_box(&payload)).unwrap());
+}
+
+#[divan::bench]
+fn deserialize_value(bencher: Bencher) {
+    let payload = payload();
+    bencher.bench(|| Webhook::deserialize(black_box(&payload)).unwrap());
+}
+
+/// Validation without `CompiledSchema`: looks through wrappers, merges
+/// constraints and compiles patterns as it meets them, and stops at the
+/// first mismatch.
+#[derive(Default)]
+struct AdHoc {
+    patterns: HashMap<String, Regex>,
+}
+
+impl AdHoc {
+    fn check(&mut self, schema: &Schema, constraints: Constraints, value: &Value) -> bool {
+        match (schema, value) {
+            (Schema::Shared(schema), _) => self.check(schema, constraints, value),
+            (
+                Schema::Branded { schema, .. }
+                | Schema::Custom { schema, .. }
+                | Schema::Ordered(schema),
+                _,
+            ) => self.check(schema, constraints, value),
+            (
+                Schema::Constrained {
+                    schema,
+                    constraints: inner,
+                },
+                _,
+            ) => self.check(schema, constraints.or(inner), value),
+            (Schema::Any, _) | (Schema::Null, Value::Null) | (Schema::Boolean, Value::Bool(_)) => {
+                true
+            }
+            (Schema::Integer, Value::Number(n)) => {
+                (n.is_i64() || n.is_u64()) && in_range(n, &constraints)
+            }
+            (Schema::Number, Value::Number(n)) => in_range(n, &constraints),
+            (Schema::NumericEnum(values), Value::Number(n)) => {
+                n.as_i64().is_some_and(|n| values.contains(&n))
+            }
+            (Schema::String, Value::String(s)) => self.text(s, &constraints),
+            (Schema::Enum(values), Value::String(s)) => values.contains(s),
+            (Schema::Bytes(BytesEncoding::Array), Value::Array(_))
+            | (Schema::Bytes(BytesEncoding::Base64 | BytesEncoding::Hex), Value::String(_)) => true,
+            (Schema::Array(item), Value::Array(items)) => {
+                has_len(items.len(), &constraints)
+                    && items
+                        .iter()
+                        .all(|value| self.check(item, Constraints::default(), value))
+            }
+            (Schema::Tuple(items), Value::Array(values)) => {
+                items.len() == values.len()
+                    && items
+                        .iter()
+                        .zip(values)
+                        .all(|(item, value)| self.check(item, Constraints::default(), value))
+            }
+            (Schema::Record { key, value: item }, Value::Object(map)) => {
+                has_len(map.len(), &constraints)
+                    && map.iter().all(|(k, v)| {
+                        self.check(key, Constraints::default(), &Value::String(k.clone()))
+                            && self.check(item, Constraints::default(), v)
+                    })
+            }
+            (Schema::Object(fields), Value::Object(map)) => {
+                fields.iter().all(|field| match map.get(&field.name) {
+                    None => !field.required,
+                    Some(Value::Null) if field.nullable => true,
+                    Some(value) => self.check(&field.schema, field.constraints.clone(), value),
+                })
+            }
+            (Schema::Union(members), _) => members
+                .iter()
+                .any(|member| self.check(member, constraints.clone(), value)),
+            _ => false,
+        }
+    }
+
+    fn text(&mut self, s: &str, constraints: &Constraints) -> bool {
+        if !has_len(s.chars().count(), constraints) {
+            return false;
+        }
+        if let Some(one_of) = &constraints.one_of {
+            if !one_of.iter().any(|allowed| allowed == s) {
+                return false;
+            }
+        }
+        match &constraints.pattern {
+            Some(pattern) => self
+                .patterns
+                .entry(pattern.clone())
+                .or_insert_with(|| Regex::new(pattern).unwrap())
+                .is_match(s),
+            None => true,
+        }
+    }
+}
+
+fn in_range(n: &Number, constraints: &Constraints) -> bool {
+    let n = n.as_f64().unwrap_or(f64::NAN);
+    constraints.min.is_none_or(|min| n >= min) && constraints.max.is_none_or(|max| n <= max)
+}
+
+fn has_len(len: usize, constraints: &Constraints) -> bool {
+    constraints.min_len.is_none_or(|min| len >= min)
+        && constraints.max_len.is_none_or(|max| len <= max)
+}
+
+#[divan::bench]
+fn ad_hoc(bencher: Bencher) {
+    let payload = payload();
+    let schema = Webhook::schema_ref();
+    assert!(AdHoc::default().check(schema, Constraints::default(), &payload));
+    bencher.bench(|| AdHoc::default().check(schema, Constraints::default(), black_box(&payload)));
+}
+
+#[divan::bench]
+fn compile_each(bencher: Bencher) {
+    let payload = payload();
+    bencher.bench(|| {
+        CompiledSchema::compile(Webhook::schema_ref())
+            .unwrap()
+            .validate(black_box(&payload))
+            .unwrap()
+    });
+}
+
+#[divan::bench]
+fn compiled(bencher: Bencher) {
+    let payload = payload();
+    let compiled = CompiledSchema::compile(Webhook::schema_ref()).unwrap();
+    bencher.bench(|| compiled.validate(black_box(&payload)).unwrap());
+}
+
+#[divan::bench]
+fn value_direct(bencher: Bencher) {
+    let webhook: Webhook = serde_json::from_value(payload()).unwrap();
+    bencher.bench(|| schema_bridge::validate_value(black_box(&webhook)).unwrap());
+}
+
+#[divan::bench]
+fn value_via_json(bencher: Bencher) {
+    let webhook: Webhook = serde_json::from_value(payload()).unwrap();
+    let compiled = CompiledSchema::compile(Webhook::schema_ref()).unwrap();
+    bencher.bench(|| {
+        let json = serde_json::to_value(black_box(&webhook)).unwrap();
+        compiled.validate(&json).unwrap()
+    });
+}
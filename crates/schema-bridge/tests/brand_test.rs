@@ -0,0 +1,54 @@
+use schema_bridge::{generate_ts_file_with, Schema, SchemaBridge, TsConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(brand)]
+struct UserId(String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(brand)]
+struct OrderId(String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Order {
+    id: OrderId,
+    owner: UserId,
+    watchers: Vec<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branded_declaration() {
+        let content = generate_ts_file_with(
+            UserId::ts_declarations("UserId"),
+            &TsConfig::new().no_banner(),
+        );
+        assert_eq!(
+            content,
+            "export type UserId = string & { readonly __brand: 'UserId' };\n\n"
+        );
+    }
+
+    #[test]
+    fn test_branded_schema_node() {
+        assert_eq!(
+            UserId::to_schema(),
+            Schema::Branded {
+                name: "UserId".to_string(),
+                schema: Box::new(Schema::String),
+            }
+        );
+        assert_eq!(UserId::to_schema().type_name(), "string");
+    }
+
+    #[test]
+    fn test_brands_are_distinct_in_fields() {
+        let ts = Order::to_ts();
+        assert!(ts.contains("id: string & { readonly __brand: 'OrderId' };"));
+        assert!(ts.contains("owner: string & { readonly __brand: 'UserId' };"));
+        assert!(ts.contains("watchers: (string & { readonly __brand: 'UserId' })[];"));
+    }
+}
@@ -0,0 +1,164 @@
+use schema_bridge::{BytesEncoding, Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+mod codec {
+    pub mod base64 {
+        use ::base64::{engine::general_purpose::STANDARD, Engine};
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&STANDARD.encode(bytes))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<u8>, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            STANDARD.decode(s).map_err(serde::de::Error::custom)
+        }
+    }
+
+    pub mod hex {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(n: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&format!("{n:x}"))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            u32::from_str_radix(&s, 16).map_err(serde::de::Error::custom)
+        }
+    }
+
+    pub mod color_hex {
+        use super::super::Color;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            let channel = |i: usize| {
+                s.get(i..i + 2)
+                    .and_then(|c| u8::from_str_radix(c, 16).ok())
+                    .ok_or_else(|| serde::de::Error::custom("invalid color"))
+            };
+            Ok(Color {
+                r: channel(1)?,
+                g: channel(3)?,
+                b: channel(5)?,
+            })
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, SchemaBridge)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+/// Helper paths only count when a whole segment names the encoding, and
+/// only on byte-typed fields
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Theme {
+    #[serde(with = "codec::color_hex")]
+    accent: Color,
+    #[serde(with = "codec::hex")]
+    seed: u32,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Upload {
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+    #[serde(with = "hex")]
+    checksum: Vec<u8>,
+    #[serde(with = "codec::base64")]
+    thumbnail: Vec<u8>,
+    raw: ByteBuf,
+    #[schema(bytes = "base64")]
+    signature: Option<Vec<u8>>,
+    plain: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Digest(#[serde(with = "hex")] [u8; 4]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_fields() {
+        let Schema::Object(fields) = Upload::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        let encodings: Vec<_> = fields.iter().map(|f| f.schema.clone()).collect();
+        assert_eq!(
+            encodings[..5],
+            [
+                Schema::Bytes(BytesEncoding::Array),
+                Schema::Bytes(BytesEncoding::Hex),
+                Schema::Bytes(BytesEncoding::Base64),
+                Schema::Bytes(BytesEncoding::Array),
+                Schema::Bytes(BytesEncoding::Base64),
+            ]
+        );
+
+        assert_eq!(
+            Upload::to_ts(),
+            "{ data: Uint8Array; checksum: string; thumbnail: string; raw: Uint8Array; signature: string | null; plain: number[]; }"
+        );
+    }
+
+    #[test]
+    fn test_newtype_bytes() {
+        assert_eq!(Digest::to_schema(), Schema::Bytes(BytesEncoding::Hex));
+        let json = serde_json::to_string(&Digest([0xde, 0xad, 0xbe, 0xef])).unwrap();
+        assert_eq!(json, "\"deadbeef\"");
+    }
+
+    #[test]
+    fn test_base64_helper_round_trip() {
+        let upload = Upload {
+            data: vec![1],
+            checksum: vec![2],
+            thumbnail: b"hi!".to_vec(),
+            raw: ByteBuf::from(vec![3]),
+            signature: None,
+            plain: vec![4],
+        };
+        let json = serde_json::to_value(&upload).unwrap();
+        assert_eq!(json["thumbnail"], "aGkh");
+        let back: Upload = serde_json::from_value(json).unwrap();
+        assert_eq!(back.thumbnail, b"hi!");
+    }
+
+    #[test]
+    fn test_only_byte_fields_are_inferred() {
+        let Schema::Object(fields) = Theme::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        assert_eq!(fields[0].schema, Color::to_schema());
+        assert_eq!(fields[1].schema, u32::to_schema());
+
+        let theme = Theme {
+            accent: Color {
+                r: 255,
+                g: 0,
+                b: 16,
+            },
+            seed: 0xabc,
+        };
+        let json = serde_json::to_value(&theme).unwrap();
+        assert_eq!(json["accent"], "#ff0010");
+        let back: Theme = serde_json::from_value(json).unwrap();
+        assert_eq!((back.accent, back.seed), (theme.accent, theme.seed));
+    }
+}
@@ -0,0 +1,99 @@
+use schema_bridge::{generate_ts_file_with, Schema, SchemaBridge, TsConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+#[schema_bridge(partial = "UserPatch", omit(id, created_at))]
+#[schema_bridge(companion = "UserSummary", pick(id, display_name))]
+struct User {
+    #[serde(skip_deserializing)]
+    id: u64,
+    #[serde(skip_deserializing)]
+    created_at: String,
+    display_name: String,
+    #[schema(min = 0, max = 150)]
+    age: u32,
+    bio: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(partial = "SettingsPatch")]
+struct Settings {
+    theme: String,
+    font_size: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(schema: &Schema) -> Vec<(&str, bool)> {
+        match schema {
+            Schema::Object(fields) => fields
+                .iter()
+                .map(|f| (f.name.as_str(), f.required))
+                .collect(),
+            _ => panic!("Expected Schema::Object"),
+        }
+    }
+
+    #[test]
+    fn test_partial_companion() {
+        let decls = User::companion_declarations();
+        assert_eq!(decls[0].name, "UserPatch");
+        assert_eq!(
+            fields(&decls[0].schema),
+            [("displayName", false), ("age", false), ("bio", false)]
+        );
+
+        // Field constraints carry over
+        let Schema::Object(patch) = &decls[0].schema else {
+            unreachable!()
+        };
+        assert_eq!(patch[1].constraints.max, Some(150.0));
+    }
+
+    #[test]
+    fn test_pick_companion() {
+        let decls = User::companion_declarations();
+        assert_eq!(decls[1].name, "UserSummary");
+        assert_eq!(
+            fields(&decls[1].schema),
+            [("id", true), ("displayName", true)]
+        );
+    }
+
+    #[test]
+    fn test_exported_with_the_type() {
+        let names: Vec<_> = User::ts_declarations("User")
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(names, ["User", "UserInput", "UserPatch", "UserSummary"]);
+
+        let ts = generate_ts_file_with(
+            Settings::ts_declarations("Settings"),
+            &TsConfig::new().no_banner(),
+        );
+        assert!(ts.contains("export type SettingsPatch = { theme?: string; font_size?: number; };"));
+    }
+
+    #[test]
+    fn test_no_companions_by_default() {
+        #[derive(Serialize, SchemaBridge)]
+        struct Plain {
+            a: String,
+        }
+        assert!(Plain::companion_declarations().is_empty());
+
+        // Just the type itself, shaped like what it writes
+        let declarations = Plain::ts_declarations("Plain");
+        assert_eq!(declarations.len(), 1);
+        let Schema::Object(fields) = &declarations[0].schema else {
+            panic!("Expected Schema::Object");
+        };
+        let json = serde_json::to_value(Plain { a: "x".into() }).unwrap();
+        let keys: Vec<_> = json.as_object().unwrap().keys().collect();
+        assert_eq!(keys, fields.iter().map(|f| &f.name).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(all(feature = "indexmap", feature = "smallvec"))]
+
+use indexmap::IndexMap;
+use schema_bridge::{Schema, SchemaBridge};
+use serde::Serialize;
+use smallvec::SmallVec;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+
+type FastMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
+#[derive(Serialize, SchemaBridge)]
+struct Settings {
+    sections: IndexMap<String, String>,
+    #[schema(items(min = 1))]
+    ports: SmallVec<[u16; 4]>,
+    cache: FastMap<String, u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_fields() {
+        assert_eq!(
+            Settings::to_ts(),
+            "{ sections: Record<string, string>; ports: number[]; cache: Record<string, number>; }"
+        );
+
+        let Schema::Object(fields) = Settings::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        let Schema::Ordered(record) = &fields[0].schema else {
+            panic!("Expected Schema::Ordered");
+        };
+        assert!(matches!(**record, Schema::Record { .. }));
+        let Schema::Array(item) = &fields[1].schema else {
+            panic!("Expected Schema::Array");
+        };
+        assert_eq!(item.constraints().min, Some(1.0));
+    }
+}
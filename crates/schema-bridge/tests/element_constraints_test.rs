@@ -0,0 +1,90 @@
+use schema_bridge::{Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Post {
+    #[schema(max_len = 10, items(min_len = 1, max_len = 32))]
+    tags: Vec<String>,
+    #[schema(keys(pattern = "^[a-z_]+$"), items(min = 0))]
+    scores: HashMap<String, i32>,
+    #[schema(items(items(min = -1.0, max = 1.0)))]
+    matrix: Vec<Vec<f64>>,
+    #[schema(items(max_len = 8))]
+    labels: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Tags(#[schema(items(min_len = 1))] Vec<String>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> Vec<schema_bridge::Field> {
+        match Post::to_schema() {
+            Schema::Object(fields) => fields,
+            _ => panic!("Expected Schema::Object"),
+        }
+    }
+
+    #[test]
+    fn test_items_constraints() {
+        let fields = fields();
+        assert_eq!(fields[0].constraints.max_len, Some(10));
+        let Schema::Array(item) = &fields[0].schema else {
+            panic!("Expected Schema::Array");
+        };
+        let c = item.constraints();
+        assert_eq!((c.min_len, c.max_len), (Some(1), Some(32)));
+    }
+
+    #[test]
+    fn test_keys_and_values() {
+        let fields = fields();
+        let Schema::Record { key, value, .. } = &fields[1].schema else {
+            panic!("Expected Schema::Record");
+        };
+        assert_eq!(key.constraints().pattern.as_deref(), Some("^[a-z_]+$"));
+        assert_eq!(value.constraints().min, Some(0.0));
+        assert!(fields[1].constraints.is_empty());
+    }
+
+    #[test]
+    fn test_nested_items() {
+        let fields = fields();
+        let Schema::Array(row) = &fields[2].schema else {
+            panic!("Expected Schema::Array");
+        };
+        let Schema::Array(cell) = &**row else {
+            panic!("Expected nested Schema::Array");
+        };
+        let c = cell.constraints();
+        assert_eq!((c.min, c.max), (Some(-1.0), Some(1.0)));
+    }
+
+    #[test]
+    fn test_items_through_option() {
+        let fields = fields();
+        assert!(fields[3].nullable);
+        let Schema::Array(item) = &fields[3].schema else {
+            panic!("Expected Schema::Array");
+        };
+        assert_eq!(item.constraints().max_len, Some(8));
+    }
+
+    #[test]
+    fn test_newtype_items() {
+        let Schema::Array(item) = Tags::to_schema() else {
+            panic!("Expected Schema::Array");
+        };
+        assert_eq!(item.constraints().min_len, Some(1));
+        assert_eq!(Tags::to_ts(), "string[]");
+    }
+
+    #[test]
+    fn test_ts_unchanged() {
+        assert!(Post::to_ts().contains("tags: string[]"));
+        assert!(Post::to_ts().contains("scores: Record<string, number>"));
+    }
+}
@@ -0,0 +1,87 @@
+use schema_bridge::{Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+
+/// A type from another crate, without a `SchemaBridge` impl
+#[derive(Serialize, Deserialize)]
+struct Money(i64);
+
+fn money_schema() -> Schema {
+    Schema::Object(vec![
+        schema_bridge::Field::new("amount", Schema::Integer),
+        schema_bridge::Field::new("currency", Schema::String),
+    ])
+}
+
+#[derive(Serialize, Deserialize)]
+struct Secret;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    user_name: String,
+    #[schema(rename = "id")]
+    account_id: u64,
+    #[schema(skip)]
+    secret: Option<Secret>,
+    #[schema(ts_type = "Money")]
+    balance: Money,
+    #[schema(ts_type = "Money", schema = money_schema)]
+    credit: Option<Money>,
+    #[schema(schema = money_schema)]
+    debt: Money,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Price(#[schema(ts_type = "Money", schema = money_schema)] Money);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> Vec<schema_bridge::Field> {
+        match Account::to_schema() {
+            Schema::Object(fields) => fields,
+            _ => panic!("Expected Schema::Object"),
+        }
+    }
+
+    #[test]
+    fn test_rename_and_skip() {
+        let names: Vec<_> = fields().into_iter().map(|f| f.name).collect();
+        assert_eq!(names, ["userName", "id", "balance", "credit", "debt"]);
+    }
+
+    #[test]
+    fn test_ts_type_override() {
+        let fields = fields();
+        assert_eq!(
+            fields[2].schema,
+            Schema::Custom {
+                ts: "Money".to_string(),
+                schema: Box::new(Schema::Any),
+            }
+        );
+        let ts = Account::to_ts();
+        assert!(ts.contains("balance: Money;"));
+        assert!(ts.contains("credit: Money | null;"));
+        assert!(!ts.contains("secret"));
+    }
+
+    #[test]
+    fn test_schema_fn_override() {
+        let fields = fields();
+        let Schema::Custom { schema, .. } = &fields[3].schema else {
+            panic!("Expected Schema::Custom");
+        };
+        assert_eq!(**schema, money_schema());
+        assert!(fields[3].nullable);
+        assert_eq!(fields[4].schema, money_schema());
+        assert!(Account::to_ts().contains("debt: { amount: number; currency: string; };"));
+    }
+
+    #[test]
+    fn test_newtype_override() {
+        assert_eq!(Price::to_ts(), "Money");
+        assert_eq!(Price::to_schema().type_name(), "table");
+    }
+}
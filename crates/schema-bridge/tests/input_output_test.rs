@@ -0,0 +1,146 @@
+use schema_bridge::{Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+
+fn default_role() -> String {
+    "member".to_string()
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    #[serde(skip_deserializing)]
+    id: u64,
+    #[serde(skip_deserializing)]
+    created_at: String,
+    name: String,
+    #[serde(skip_serializing)]
+    password: String,
+    #[serde(default = "default_role")]
+    role: String,
+    #[serde(rename(serialize = "displayName", deserialize = "display"))]
+    display_name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Team {
+    name: String,
+    members: Vec<User>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(default)]
+struct Settings {
+    theme: String,
+    #[serde(rename = "fontSize")]
+    font_size: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            theme: "light".to_string(),
+            font_size: 14,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(schema: Schema) -> Vec<(String, bool)> {
+        match schema {
+            Schema::Object(fields) => fields.into_iter().map(|f| (f.name, f.required)).collect(),
+            _ => panic!("Expected Schema::Object"),
+        }
+    }
+
+    fn field(name: &str, required: bool) -> (String, bool) {
+        (name.to_string(), required)
+    }
+
+    #[test]
+    fn test_output_schema() {
+        assert_eq!(
+            fields(User::to_schema()),
+            [
+                field("id", true),
+                field("createdAt", true),
+                field("name", true),
+                field("role", true),
+                field("displayName", true),
+                field("tags", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_schema() {
+        assert_eq!(
+            fields(User::to_schema_input()),
+            [
+                field("name", true),
+                field("password", true),
+                field("role", false),
+                field("display", true),
+                field("tags", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_schema_matches_serde() {
+        let user: User =
+            serde_json::from_str(r#"{"name": "Ann", "password": "pw", "display": "A"}"#).unwrap();
+        assert_eq!(user.password, "pw");
+        assert_eq!(user.role, "member");
+        assert_eq!(user.id, 0);
+    }
+
+    #[test]
+    fn test_nested_input_schema() {
+        let Schema::Object(team) = Team::to_schema_input() else {
+            panic!("Expected Schema::Object");
+        };
+        assert_eq!(
+            team[1].schema,
+            Schema::Array(Box::new(User::to_schema_input()))
+        );
+    }
+
+    #[test]
+    fn test_container_default() {
+        assert_eq!(
+            fields(Settings::to_schema()),
+            [field("theme", true), field("fontSize", true)]
+        );
+        assert_eq!(
+            fields(Settings::to_schema_input()),
+            [field("theme", false), field("fontSize", false)]
+        );
+    }
+
+    #[test]
+    fn test_input_declaration_pairs() {
+        let names: Vec<_> = User::ts_declarations("User")
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(names, ["User", "UserInput"]);
+        assert_eq!(Team::ts_declarations("Team").len(), 2);
+    }
+
+    #[test]
+    fn test_symmetric_types_have_one_declaration() {
+        assert_eq!(Point::to_schema_input(), Point::to_schema());
+        assert_eq!(Point::ts_declarations("Point").len(), 1);
+    }
+}
@@ -0,0 +1,38 @@
+use schema_bridge::{generate_ts_file_with, AnyStyle, Schema, SchemaBridge, TsConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Event {
+    kind: String,
+    payload: serde_json::Value,
+    extensions: serde_json::Map<String, serde_json::Value>,
+    metadata: Option<serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_schema() {
+        assert_eq!(serde_json::Value::to_schema(), Schema::Any);
+        assert_eq!(Event::to_ts(), "{ kind: string; payload: any; extensions: Record<string, any>; metadata: any | null; }");
+    }
+
+    #[test]
+    fn test_json_value_alias() {
+        let ts = generate_ts_file_with(
+            Event::ts_declarations("Event"),
+            &TsConfig::new().no_banner().any_style(AnyStyle::JsonValue),
+        );
+        assert!(ts.starts_with("export type JsonValue = "));
+        assert!(ts.contains("payload: JsonValue;"));
+        assert!(ts.contains("extensions: Record<string, JsonValue>;"));
+    }
+
+    #[cfg(feature = "raw_value")]
+    #[test]
+    fn test_raw_value() {
+        assert_eq!(Box::<serde_json::value::RawValue>::to_schema(), Schema::Any);
+    }
+}
@@ -0,0 +1,86 @@
+use schema_bridge::{Schema, SchemaBridge, SchemaKey};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Serialize, Deserialize, SchemaBridge, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Active,
+    OnHold,
+}
+
+#[derive(Serialize_repr, Deserialize_repr, SchemaBridge, PartialEq, Eq, Hash)]
+#[schema_bridge(numeric)]
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    High = 2,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge, PartialEq, Eq, Hash)]
+#[schema_bridge(brand)]
+struct UserId(String);
+
+#[derive(Serialize, Deserialize, SchemaBridge, PartialEq, Eq, Hash)]
+struct Port(u16);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Dashboard {
+    by_status: BTreeMap<Status, u32>,
+    by_level: HashMap<Level, u32>,
+    by_user: HashMap<UserId, String>,
+    by_port: HashMap<Port, String>,
+    by_id: HashMap<u64, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enum_keys_are_partial() {
+        assert_eq!(
+            HashMap::<Status, u32>::to_ts(),
+            "Partial<Record<'active' | 'on_hold', number>>"
+        );
+        assert_eq!(
+            HashMap::<Level, u32>::to_ts(),
+            "Partial<Record<'1' | '2', number>>"
+        );
+
+        let json = serde_json::to_string(&HashMap::from([(Level::High, 1)])).unwrap();
+        assert_eq!(json, r#"{"2":1}"#);
+    }
+
+    #[test]
+    fn test_newtype_keys() {
+        assert_eq!(UserId::to_key_schema(), UserId::to_schema());
+        assert_eq!(
+            HashMap::<UserId, String>::to_ts(),
+            "Record<string & { readonly __brand: 'UserId' }, string>"
+        );
+        assert_eq!(Port::to_key_schema(), u16::to_key_schema());
+    }
+
+    #[test]
+    fn test_integer_keys_are_strings() {
+        let Schema::Object(fields) = Dashboard::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        let Schema::Record { key, .. } = &fields[4].schema else {
+            panic!("Expected Schema::Record");
+        };
+        assert_eq!(key.constraints().pattern.as_deref(), Some("^[0-9]+$"));
+        assert_eq!(
+            Dashboard::to_ts(),
+            "{ by_status: Partial<Record<'active' | 'on_hold', number>>; \
+             by_level: Partial<Record<'1' | '2', number>>; \
+             by_user: Record<string & { readonly __brand: 'UserId' }, string>; \
+             by_port: Record<string, string>; by_id: Record<string, string>; }"
+        );
+
+        let json = serde_json::to_string(&HashMap::from([(7u64, "x")])).unwrap();
+        assert_eq!(json, r#"{"7":"x"}"#);
+    }
+}
@@ -0,0 +1,75 @@
+use schema_bridge::{Constraints, Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Name(#[schema(min_len = 1, max_len = 64)] String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema(min = 0, max = 150)]
+struct Age(u8);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema(max_len = 16)]
+struct Handle(#[schema(min_len = 3, max_len = 32)] String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(brand)]
+#[schema(min_len = 26, max_len = 26)]
+struct Ulid(String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Person {
+    name: Name,
+    age: Age,
+    nickname: Option<Name>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inner_field_constraints() {
+        assert_eq!(
+            Name::to_schema(),
+            Schema::Constrained {
+                schema: Box::new(Schema::String),
+                constraints: Constraints::default().min_len(1).max_len(64),
+            }
+        );
+        assert_eq!(Name::to_ts(), "string");
+    }
+
+    #[test]
+    fn test_container_constraints() {
+        let c = Age::to_schema().constraints();
+        assert_eq!(c.min, Some(0.0));
+        assert_eq!(c.max, Some(150.0));
+    }
+
+    #[test]
+    fn test_container_wins_over_inner_field() {
+        let c = Handle::to_schema().constraints();
+        assert_eq!(c.min_len, Some(3));
+        assert_eq!(c.max_len, Some(16));
+    }
+
+    #[test]
+    fn test_branded_constrained_newtype() {
+        let schema = Ulid::to_schema();
+        assert!(matches!(schema, Schema::Branded { .. }));
+        assert_eq!(schema.constraints().min_len, Some(26));
+        assert_eq!(Ulid::to_ts(), "string & { readonly __brand: 'Ulid' }");
+    }
+
+    #[test]
+    fn test_fields_inherit_type_constraints() {
+        let Schema::Object(fields) = Person::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        assert_eq!(fields[0].schema.constraints().max_len, Some(64));
+        assert_eq!(fields[1].schema.constraints().max, Some(150.0));
+        assert_eq!(fields[2].schema.constraints().min_len, Some(1));
+        assert!(fields[0].constraints.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use schema_bridge::{generate_ts_file_with, Schema, SchemaBridge, TsConfig};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Serialize_repr, Deserialize_repr, SchemaBridge, Debug, PartialEq)]
+#[schema_bridge(numeric)]
+#[repr(u8)]
+enum DeviceState {
+    Off = 0,
+    Standby = 1,
+    On = 4,
+    Fault,
+}
+
+#[derive(Serialize_repr, Deserialize_repr, SchemaBridge)]
+#[repr(i16)]
+#[schema_bridge(numeric, ts_enum, ts_const_values)]
+enum Offset {
+    Back = -1,
+    Stay,
+    Forward,
+}
+
+// Without the marker a repr enum keeps the string form
+#[derive(serde::Serialize, serde::Deserialize, SchemaBridge)]
+#[repr(u8)]
+enum Plain {
+    A,
+    B,
+}
+
+// serde's own derive serializes a repr enum by variant name
+#[derive(serde::Serialize, serde::Deserialize, SchemaBridge)]
+#[repr(u8)]
+enum Named {
+    Low = 1,
+    High = BASE + 1,
+}
+
+const BASE: u8 = 8;
+
+/// Serialized by hand as its discriminant
+#[derive(SchemaBridge, Clone, Copy)]
+#[schema_bridge(numeric)]
+#[repr(u8)]
+enum Priority {
+    Low = 1,
+    High = 3,
+}
+
+impl serde::Serialize for Priority {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_enum_schema() {
+        assert_eq!(
+            DeviceState::to_schema(),
+            Schema::NumericEnum(vec![0, 1, 4, 5])
+        );
+        assert_eq!(DeviceState::to_ts(), "0 | 1 | 4 | 5");
+    }
+
+    #[test]
+    fn test_schema_matches_serde_repr() {
+        assert_eq!(serde_json::to_string(&DeviceState::Fault).unwrap(), "5");
+        let state: DeviceState = serde_json::from_str("4").unwrap();
+        assert_eq!(state, DeviceState::On);
+    }
+
+    #[test]
+    fn test_negative_discriminants_and_declarations() {
+        assert_eq!(
+            Offset::to_schema(),
+            Schema::Custom {
+                ts: "Offset".to_string(),
+                schema: Box::new(Schema::NumericEnum(vec![-1, 0, 1])),
+            }
+        );
+
+        let content = generate_ts_file_with(
+            Offset::ts_declarations("Offset"),
+            &TsConfig::new().no_banner(),
+        );
+        assert_eq!(
+            content,
+            "export enum Offset { Back = -1, Stay = 0, Forward = 1 }\n\n\
+             export const OFFSET_VALUES = [-1, 0, 1] as const;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_repr_without_discriminants_stays_string() {
+        assert_eq!(
+            Plain::to_schema(),
+            Schema::Enum(vec!["A".to_string(), "B".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_repr_with_serde_derive_stays_string() {
+        assert_eq!(
+            Named::to_schema(),
+            Schema::Enum(vec!["Low".to_string(), "High".to_string()])
+        );
+        assert_eq!(serde_json::to_string(&Named::High).unwrap(), "\"High\"");
+        assert_eq!(Named::Low as u8, 1);
+    }
+
+    #[test]
+    fn test_numeric_attribute() {
+        assert_eq!(Priority::to_schema(), Schema::NumericEnum(vec![1, 3]));
+        assert_eq!(serde_json::to_string(&Priority::High).unwrap(), "3");
+        assert_eq!(serde_json::to_string(&Priority::Low).unwrap(), "1");
+    }
+}
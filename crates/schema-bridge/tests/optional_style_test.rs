@@ -0,0 +1,64 @@
+use schema_bridge::{render_ts, Declaration, OptionalStyle, Schema, SchemaBridge, TsConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Contact {
+    name: String,
+    email: Option<String>,
+    #[schema(optional_style = "optional")]
+    phone: Option<String>,
+    #[schema(optional_style = "optional_nullable")]
+    fax: Option<String>,
+    #[schema(required)]
+    website: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy() {
+        assert_eq!(
+            Contact::to_ts(),
+            "{ name: string; email: string | null; phone?: string; fax?: string | null; website: string | null; }"
+        );
+    }
+
+    #[test]
+    fn test_to_ts_agrees_with_schema_renderer() {
+        assert_eq!(
+            Contact::to_ts(),
+            render_ts(&Contact::to_schema(), &TsConfig::new())
+        );
+    }
+
+    #[test]
+    fn test_global_policy_respects_field_override() {
+        let config = TsConfig::new().optional_style(OptionalStyle::Optional);
+        assert_eq!(
+            render_ts(&Contact::to_schema(), &config),
+            "{ name: string; email?: string; phone?: string; fax?: string | null; website: string | null; }"
+        );
+    }
+
+    #[test]
+    fn test_schema_records_optionality() {
+        let Schema::Object(fields) = Contact::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        assert!(fields[1].nullable && !fields[1].required);
+        assert_eq!(fields[2].optional_style, Some(OptionalStyle::Optional));
+        assert!(fields[4].nullable && fields[4].required);
+        assert!(!fields[0].nullable);
+    }
+
+    #[test]
+    fn test_declaration_with_policy() {
+        let config = TsConfig::new().optional_style(OptionalStyle::OptionalNullable);
+        let decl = Declaration::new("Contact", Contact::to_schema());
+        assert!(
+            schema_bridge::render_declaration(&decl, &config).contains("email?: string | null;")
+        );
+    }
+}
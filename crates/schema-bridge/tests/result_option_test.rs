@@ -0,0 +1,64 @@
+use schema_bridge::{OptionalStyle, Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Outcome {
+    result: Result<u32, String>,
+    #[schema(untagged_result)]
+    flattened: Result<u32, String>,
+    #[schema(untagged_result)]
+    batch: Vec<Result<bool, String>>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Patch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nickname: Option<Option<String>>,
+    #[schema(optional_style = "nullable")]
+    avatar: Option<Option<String>>,
+    age: Option<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_matches_serde() {
+        let json = serde_json::to_value(Outcome {
+            result: Ok(1),
+            flattened: Err("no".into()),
+            batch: vec![Ok(true)],
+        })
+        .unwrap();
+        assert_eq!(json["result"], serde_json::json!({ "Ok": 1 }));
+
+        assert_eq!(
+            Outcome::to_ts(),
+            "{ result: { Ok: number; } | { Err: string; }; flattened: number | string; batch: (boolean | string)[]; }"
+        );
+    }
+
+    #[test]
+    fn test_double_option_field() {
+        let Schema::Object(fields) = Patch::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        assert_eq!(fields[0].schema, Schema::String);
+        assert!(fields[0].nullable && !fields[0].required);
+        assert_eq!(
+            fields[0].optional_style,
+            Some(OptionalStyle::OptionalNullable)
+        );
+
+        assert_eq!(
+            Patch::to_ts(),
+            "{ nickname?: string | null; avatar: string | null; age: number | null; }"
+        );
+    }
+
+    #[test]
+    fn test_nested_option_value() {
+        assert_eq!(Option::<Option<u8>>::to_ts(), "number | null");
+    }
+}
@@ -0,0 +1,164 @@
+use schema_bridge::{render_ts, Schema, SchemaBridge, TsConfig};
+use serde::{Deserialize, Serialize};
+use std::ptr;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Address {
+    city: String,
+    zip: String,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Customer {
+    name: String,
+    address: Address,
+    #[serde(skip_deserializing)]
+    id: u64,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Route {
+    stops: Vec<Address>,
+    depot: Option<Address>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Tree {
+    name: String,
+    children: Vec<Tree>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Comment {
+    body: String,
+    #[serde(skip_deserializing)]
+    votes: u32,
+    reply: Option<Box<Comment>>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Folder {
+    files: Vec<File>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct File {
+    parent: Option<Folder>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_ref_is_built_once() {
+        let first = Customer::schema_ref();
+        assert!(ptr::eq(first, Customer::schema_ref()));
+        assert_eq!(*first, Customer::to_schema());
+    }
+
+    #[test]
+    fn test_symmetric_types_share_one_tree() {
+        assert!(ptr::eq(Address::schema_ref(), Address::schema_input_ref()));
+
+        assert!(!ptr::eq(
+            Customer::schema_ref(),
+            Customer::schema_input_ref()
+        ));
+        assert_eq!(*Customer::schema_input_ref(), Customer::to_schema_input());
+    }
+
+    #[test]
+    fn test_generic_and_std_types_use_the_shared_cache() {
+        let strings = Page::<String>::schema_ref();
+        assert!(ptr::eq(strings, Page::<String>::schema_ref()));
+        assert_ne!(strings, Page::<u8>::schema_ref());
+
+        assert!(ptr::eq(Vec::<u8>::schema_ref(), Vec::<u8>::schema_ref()));
+        assert_eq!(*str::schema_ref(), Schema::String);
+    }
+
+    #[test]
+    fn test_schema_ref_across_threads() {
+        let refs: Vec<usize> = (0..8)
+            .map(|_| std::thread::spawn(|| Page::<Address>::schema_ref() as *const Schema as usize))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert!(refs.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    #[test]
+    fn test_nested_types_reference_the_cached_tree() {
+        let Schema::Object(fields) = Customer::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        let Schema::Shared(address) = fields[1].schema else {
+            panic!("Expected Schema::Shared");
+        };
+        assert!(ptr::eq(address, Address::schema_ref()));
+
+        let Schema::Object(fields) = Route::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        let Schema::Array(stop) = &fields[0].schema else {
+            panic!("Expected Schema::Array");
+        };
+        assert!(matches!(**stop, Schema::Shared(s) if ptr::eq(s, Address::schema_ref())));
+        assert_eq!(fields[1].schema, Address::to_schema());
+    }
+
+    #[test]
+    fn test_shared_schemas_look_like_their_target() {
+        let shared = Address::shared_schema();
+        assert_eq!(shared, Address::to_schema());
+        assert_eq!(shared.resolved(), Address::schema_ref());
+        assert_eq!(
+            serde_json::to_value(&shared).unwrap(),
+            serde_json::to_value(Address::to_schema()).unwrap()
+        );
+        assert_eq!(
+            Customer::to_ts(),
+            render_ts(&Customer::to_schema(), &TsConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_recursive_types_refer_to_themselves_by_name() {
+        let children = Schema::Array(Box::new(Schema::Ref("Tree".into())));
+        let Schema::Object(fields) = Tree::schema_ref() else {
+            panic!("Expected Schema::Object");
+        };
+        assert_eq!(fields[1].schema, children);
+        assert_eq!(Tree::to_schema(), *Tree::schema_ref());
+        assert_eq!(Tree::to_ts(), "{ name: string; children: Tree[]; }");
+    }
+
+    #[test]
+    fn test_recursive_input_refers_to_the_input_declaration() {
+        let declarations = Comment::ts_declarations("Comment");
+        let input = render_ts(&declarations[1].schema, &TsConfig::default());
+        assert_eq!(declarations[1].name, "CommentInput");
+        assert_eq!(input, "{ body: string; reply: CommentInput | null; }");
+        assert_eq!(
+            Comment::to_ts(),
+            "{ body: string; votes: number; reply: Comment | null; }"
+        );
+    }
+
+    #[test]
+    fn test_mutually_recursive_types() {
+        assert_eq!(Folder::to_ts(), "{ files: { parent: Folder | null; }[]; }");
+        // Built after `Folder`, `File` nests its cached tree
+        assert_eq!(
+            File::to_ts(),
+            "{ parent: { files: { parent: Folder | null; }[]; } | null; }"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use schema_bridge::{Field, Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(transparent)]
+struct Email {
+    #[schema(min_len = 3)]
+    address: String,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(transparent)]
+struct Tagged {
+    value: u32,
+    #[serde(skip)]
+    _marker: PhantomData<String>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(transparent)]
+struct Marked {
+    value: u32,
+    _marker: PhantomData<String>,
+    #[serde(skip_serializing, skip_deserializing)]
+    cache: Option<String>,
+}
+
+/// Wire format of `Point`
+#[derive(Clone, Serialize, Deserialize, SchemaBridge)]
+struct PointWire {
+    coords: (f64, f64),
+}
+
+#[derive(Clone, Serialize, Deserialize, SchemaBridge)]
+#[serde(into = "PointWire", from = "PointWire")]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl From<Point> for PointWire {
+    fn from(p: Point) -> Self {
+        PointWire { coords: (p.x, p.y) }
+    }
+}
+
+impl From<PointWire> for Point {
+    fn from(w: PointWire) -> Self {
+        Point {
+            x: w.coords.0,
+            y: w.coords.1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(try_from = "String")]
+struct Slug {
+    parts: Vec<String>,
+}
+
+impl TryFrom<String> for Slug {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Ok(Slug {
+            parts: s.split('-').map(str::to_string).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transparent_struct() {
+        assert_eq!(Email::to_ts(), "string");
+        assert_eq!(Email::to_schema().constraints().min_len, Some(3));
+        assert_eq!(
+            serde_json::to_string(&Email {
+                address: "a@b".into()
+            })
+            .unwrap(),
+            "\"a@b\""
+        );
+    }
+
+    #[test]
+    fn test_transparent_with_skipped_fields() {
+        assert_eq!(Tagged::to_schema(), Schema::Integer);
+        assert_eq!(Marked::to_schema(), Schema::Integer);
+        let marked: Marked = serde_json::from_str("7").unwrap();
+        assert_eq!((marked.value, marked.cache), (7, None));
+    }
+
+    #[test]
+    fn test_into_proxy() {
+        assert_eq!(Point::to_schema(), PointWire::to_schema());
+        assert_eq!(Point::to_schema_input(), PointWire::to_schema());
+        assert_eq!(Point::to_ts(), "{ coords: [number, number]; }");
+
+        let json = serde_json::to_value(Point { x: 1.0, y: 2.0 }).unwrap();
+        assert_eq!(json, serde_json::json!({ "coords": [1.0, 2.0] }));
+    }
+
+    #[test]
+    fn test_try_from_proxy() {
+        // Deserialized through `String`, serialized as its own fields
+        assert_eq!(Slug::to_schema_input(), Schema::String);
+        assert!(matches!(Slug::to_schema(), Schema::Object(_)));
+        let slug: Slug = serde_json::from_str("\"a-b\"").unwrap();
+        assert_eq!(slug.parts, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_plain_struct_unchanged() {
+        assert_eq!(
+            PointWire::to_schema(),
+            Schema::Object(vec![Field::new(
+                "coords",
+                Schema::Tuple(vec![Schema::Number, Schema::Number])
+            )])
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use schema_bridge::{Schema, SchemaBridge};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::num::NonZeroU16;
+use std::time::Duration;
+
+#[derive(Serialize, SchemaBridge)]
+struct Borrowed<'a> {
+    name: &'a str,
+    label: Cow<'a, str>,
+    tags: &'a [String],
+}
+
+#[derive(Serialize, SchemaBridge)]
+struct Page<T> {
+    items: Vec<T>,
+    cursor: Option<String>,
+}
+
+#[derive(Serialize, SchemaBridge)]
+struct Tagged<T: Clone> {
+    value: T,
+    marker: PhantomData<T>,
+}
+
+#[derive(Serialize, SchemaBridge)]
+struct Peer {
+    addr: IpAddr,
+    port: NonZeroU16,
+    timeout: Duration,
+    position: [f32; 2],
+    queue: VecDeque<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_fields() {
+        assert_eq!(
+            Borrowed::to_ts(),
+            "{ name: string; label: string; tags: string[]; }"
+        );
+    }
+
+    #[test]
+    fn test_generic_struct() {
+        assert_eq!(
+            Page::<bool>::to_ts(),
+            "{ items: boolean[]; cursor: string | null; }"
+        );
+        assert_eq!(Tagged::<u8>::to_ts(), "{ value: number; marker: null; }");
+    }
+
+    #[test]
+    fn test_std_field_types() {
+        assert_eq!(
+            Peer::to_ts(),
+            "{ addr: string; port: number; timeout: { secs: number; nanos: number; }; position: [number, number]; queue: number[]; }"
+        );
+
+        let Schema::Object(fields) = Peer::to_schema() else {
+            panic!("Expected Schema::Object");
+        };
+        assert_eq!(fields[1].schema.constraints().min, Some(1.0));
+    }
+}
@@ -23,6 +23,12 @@ struct SystemSettings {
     cache_enabled: bool,
 }
 
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StrictConfig {
+    retry_limit: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +72,9 @@ mod tests {
             "Should not contain 'database_url:'"
         );
     }
+
+    #[test]
+    fn test_rename_all_alongside_other_serde_options() {
+        assert!(StrictConfig::to_ts().contains("retryLimit:"));
+    }
 }
@@ -0,0 +1,62 @@
+#![cfg(all(feature = "time", feature = "validate"))]
+
+use schema_bridge::{CompiledSchema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Booking {
+    day: Date,
+    opens: Time,
+    starts: PrimitiveDateTime,
+    created: OffsetDateTime,
+}
+
+fn booking() -> Booking {
+    let day = Date::from_calendar_date(2024, Month::February, 29).unwrap();
+    let opens = Time::from_hms_milli(9, 30, 0, 250).unwrap();
+    let starts = PrimitiveDateTime::new(day, opens);
+    let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+    Booking {
+        day,
+        opens,
+        starts,
+        created: starts.assume_offset(offset),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialized_values_validate() {
+        let json = serde_json::to_value(booking()).unwrap();
+        assert!(
+            json["day"].is_string(),
+            "expected human-readable strings: {json}"
+        );
+
+        let compiled = CompiledSchema::compile(Booking::schema_ref()).unwrap();
+        assert_eq!(compiled.validate(&json), Ok(()), "{json}");
+
+        let back: Booking = serde_json::from_value(json).unwrap();
+        assert_eq!(back.created, booking().created);
+    }
+
+    #[test]
+    fn test_other_strings_are_rejected() {
+        let mut json = serde_json::to_value(booking()).unwrap();
+        json["created"] = "2024-02-29T09:30:00Z".into();
+        json["day"] = "29/02/2024".into();
+
+        let compiled = CompiledSchema::compile(Booking::schema_ref()).unwrap();
+        let paths: Vec<_> = compiled
+            .validate(&json)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.path)
+            .collect();
+        assert_eq!(paths, ["created", "day"]);
+    }
+}
@@ -0,0 +1,91 @@
+use schema_bridge::{
+    generate_ts_file_with, Declaration, DeclarationStyle, QuoteStyle, SchemaBridge, TsConfig,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    user_name: String,
+    login_count: i32,
+    roles: Vec<Role>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+enum Role {
+    Admin,
+    Member,
+}
+
+fn declarations() -> Vec<Declaration> {
+    vec![
+        Declaration::new("Account", Account::to_schema()),
+        Declaration::new("Role", Role::to_schema()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_single_line() {
+        let content = generate_ts_file_with(declarations(), &TsConfig::new());
+        assert!(content.starts_with("// This file is auto-generated by schema-bridge\n\n"));
+        assert!(content.contains(
+            "export type Account = { userName: string; loginCount: number; roles: ('Admin' | 'Member')[]; };"
+        ));
+        assert!(content.contains("export type Role = 'Admin' | 'Member';"));
+    }
+
+    #[test]
+    fn test_pretty_interface_output() {
+        let config = TsConfig::new()
+            .pretty(true)
+            .declaration_style(DeclarationStyle::Interface)
+            .readonly(true)
+            .quote_style(QuoteStyle::Double)
+            .semicolons(false)
+            .banner("// Generated. Do not edit.")
+            .header("/* eslint-disable */");
+
+        let content = generate_ts_file_with(declarations(), &config);
+        assert_eq!(
+            content,
+            "// Generated. Do not edit.\n\
+             /* eslint-disable */\n\n\
+             export interface Account {\n  \
+             readonly userName: string\n  \
+             readonly loginCount: number\n  \
+             readonly roles: (\"Admin\" | \"Member\")[]\n\
+             }\n\n\
+             export type Role = \"Admin\" | \"Member\"\n\n"
+        );
+    }
+
+    #[test]
+    fn test_declare_style() {
+        let config = TsConfig::new().declare(true);
+        let content = generate_ts_file_with(declarations(), &config);
+        assert!(content.contains("export declare type Account = {"));
+        assert!(content.contains("export declare type Role = 'Admin' | 'Member';"));
+    }
+
+    #[test]
+    fn test_export_types_with_config() {
+        let path = std::env::temp_dir().join("schema_bridge_ts_config_test.ts");
+        let path = path.to_str().unwrap();
+
+        schema_bridge::export_types!(
+            config = TsConfig::new().pretty(true).indent(4),
+            path,
+            Account,
+            Role
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains("export type Account = {\n    userName: string;\n"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
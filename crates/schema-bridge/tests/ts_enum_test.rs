@@ -0,0 +1,101 @@
+use schema_bridge::{generate_ts_file_with, Schema, SchemaBridge, TsConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(ts_enum)]
+#[serde(rename_all = "snake_case")]
+enum OrderStatus {
+    Pending,
+    InTransit,
+    Delivered,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Order {
+    status: OrderStatus,
+    history: Vec<OrderStatus>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(ts_const_values)]
+enum Theme {
+    Light,
+    Dark,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge, Debug, PartialEq)]
+#[schema_bridge(ts_enum, ts_const_values, string_conversion)]
+enum Level {
+    Low,
+    High,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export<T: SchemaBridge>(name: &str) -> String {
+        generate_ts_file_with(T::ts_declarations(name), &TsConfig::new().no_banner())
+    }
+
+    #[test]
+    fn test_ts_enum() {
+        assert_eq!(
+            export::<OrderStatus>("OrderStatus"),
+            "export enum OrderStatus { Pending = 'pending', InTransit = 'in_transit', Delivered = 'delivered' }\n\n"
+        );
+        // Inline uses name the enum rather than repeating its values
+        assert_eq!(OrderStatus::to_ts(), "OrderStatus");
+        assert_eq!(
+            Order::to_ts(),
+            "{ status: OrderStatus; history: OrderStatus[]; }"
+        );
+        assert_eq!(
+            HashMap::<OrderStatus, u32>::to_ts(),
+            "Partial<Record<OrderStatus, number>>"
+        );
+        // Validation still checks the values
+        assert_eq!(
+            OrderStatus::to_schema(),
+            Schema::Custom {
+                ts: "OrderStatus".to_string(),
+                schema: Box::new(Schema::Enum(vec![
+                    "pending".to_string(),
+                    "in_transit".to_string(),
+                    "delivered".to_string()
+                ])),
+            }
+        );
+    }
+
+    #[test]
+    fn test_ts_const_values() {
+        assert_eq!(
+            export::<Theme>("Theme"),
+            "export type Theme = 'Light' | 'Dark';\n\n\
+             export const THEME_VALUES = ['Light', 'Dark'] as const;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_ts_enum_with_const_values() {
+        let content = export::<Level>("Level");
+        assert!(content.contains("export enum Level { Low = 'Low', High = 'High' }"));
+        assert!(content.contains("export const LEVEL_VALUES = ['Low', 'High'] as const;"));
+        assert_eq!(Level::High.to_string(), "High");
+    }
+
+    #[test]
+    fn test_export_types_uses_declarations() {
+        let path = std::env::temp_dir().join("schema_bridge_ts_enum_test.ts");
+        let path = path.to_str().unwrap();
+
+        schema_bridge::export_types!(path, OrderStatus, Theme).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains("export enum OrderStatus {"));
+        assert!(content.contains("export const THEME_VALUES"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use schema_bridge::{Schema, SchemaBridge};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+type Spectrum = (
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+);
+
+// Positional record with serde's maximum tuple arity
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Frame(
+    u64,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    Spectrum,
+);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Marker;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Empty();
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Sample<T>(f64, f64, #[serde(skip)] PhantomData<T>);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Reading(f64, #[serde(skip_deserializing)] String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Pair(
+    #[schema(min_len = 1)] String,
+    #[schema(min = 1, max = 5)] u32,
+    #[schema(items(pattern = "^[a-z]+$"))] Vec<String>,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sixteen_element_tuple_struct() {
+        let Schema::Tuple(items) = Frame::to_schema() else {
+            panic!("Expected Schema::Tuple");
+        };
+        assert_eq!(items.len(), 16);
+        assert!(matches!(&items[15], Schema::Tuple(inner) if inner.len() == 16));
+    }
+
+    #[test]
+    fn test_zero_sized_structs() {
+        assert_eq!(serde_json::to_string(&Marker).unwrap(), "null");
+        assert_eq!(Marker::to_ts(), "null");
+
+        assert_eq!(serde_json::to_string(&Empty()).unwrap(), "[]");
+        assert_eq!(Empty::to_ts(), "[]");
+    }
+
+    #[test]
+    fn test_skipped_tuple_fields() {
+        let sample: Sample<u8> = Sample(1.0, 2.0, PhantomData);
+        assert_eq!(serde_json::to_string(&sample).unwrap(), "[1.0,2.0]");
+        assert_eq!(Sample::<u8>::to_ts(), "[number, number]");
+
+        assert_eq!(
+            Reading::to_schema(),
+            Schema::Tuple(vec![Schema::Number, Schema::String])
+        );
+        assert_eq!(
+            Reading::to_schema_input(),
+            Schema::Tuple(vec![Schema::Number])
+        );
+    }
+
+    #[test]
+    fn test_tuple_field_constraints() {
+        let Schema::Tuple(members) = Pair::to_schema() else {
+            panic!("Expected Schema::Tuple");
+        };
+        assert_eq!(members[0].constraints().min_len, Some(1));
+        assert_eq!(members[1].constraints().min, Some(1.0));
+        assert_eq!(members[1].constraints().max, Some(5.0));
+        let Schema::Array(item) = members[2].resolved() else {
+            panic!("Expected Schema::Array");
+        };
+        assert_eq!(item.constraints().pattern.as_deref(), Some("^[a-z]+$"));
+        assert_eq!(Pair::to_ts(), "[string, number, string[]]");
+    }
+}
@@ -0,0 +1,29 @@
+use schema_bridge::SchemaBridge;
+use std::collections::HashMap;
+
+#[derive(SchemaBridge)]
+struct User {
+    #[schema(max_len = 10)]
+    age: u8,
+    #[schema(min = 1)]
+    name: Option<String>,
+    #[schema(one_of("a", "b"))]
+    tags: Vec<String>,
+    #[schema(items(min = 0))]
+    labels: Vec<String>,
+    #[schema(keys(min_len = 1))]
+    scores: Vec<u32>,
+    #[schema(keys(pattern = "^[a-z]+$"), items(pattern = "^x"))]
+    counts: HashMap<String, u32>,
+    #[schema(untagged_result)]
+    done: bool,
+    #[schema(min = 0)]
+    outcome: Result<u8, String>,
+    #[schema(bytes = "hex")]
+    token: String,
+}
+
+#[derive(SchemaBridge)]
+struct Count(#[schema(pattern = "^[0-9]+$")] u64);
+
+fn main() {}
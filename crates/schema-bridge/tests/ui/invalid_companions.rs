@@ -0,0 +1,21 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+#[schema_bridge(partial = "UserPatch", omit(idd))]
+struct User {
+    id: u64,
+}
+
+#[derive(SchemaBridge)]
+#[schema_bridge(pick(id))]
+struct Post {
+    id: u64,
+}
+
+#[derive(SchemaBridge)]
+#[schema_bridge(partial = "StatusPatch")]
+enum Status {
+    Active,
+}
+
+fn main() {}
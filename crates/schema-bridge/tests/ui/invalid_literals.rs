@@ -0,0 +1,25 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+struct User {
+    #[schema(min_len = "3")]
+    name: String,
+    #[schema(max = "150")]
+    age: u8,
+}
+
+#[derive(SchemaBridge)]
+struct Settings {
+    #[schema(one_of(1, 2))]
+    mode: String,
+    #[schema(optional_style = "maybe")]
+    theme: Option<String>,
+    #[schema(bytes = "utf8")]
+    avatar: Vec<u8>,
+    #[schema(pattern = "^[a-z+$")]
+    slug: String,
+    #[schema(items(pattern = "(unclosed"))]
+    tags: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use schema_bridge::SchemaBridge;
+use std::collections::HashMap;
+
+#[derive(SchemaBridge, PartialEq, Eq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(SchemaBridge, PartialEq, Eq, Hash)]
+#[serde(tag = "kind")]
+enum Shape {
+    Circle,
+    Square,
+}
+
+#[derive(SchemaBridge)]
+struct Grid {
+    cells: HashMap<Point, String>,
+    rows: HashMap<Vec<u8>, String>,
+    shapes: HashMap<Shape, u32>,
+}
+
+fn main() {}
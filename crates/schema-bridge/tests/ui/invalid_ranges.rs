@@ -0,0 +1,20 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+struct User {
+    #[schema(min = 10, max = 5)]
+    age: u8,
+    #[schema(max_len = 3, min_len = 8)]
+    name: String,
+}
+
+// Each side is fine alone; the merged bounds are not
+#[derive(SchemaBridge)]
+#[schema(max = 5)]
+struct Small(#[schema(min = 10)] u8);
+
+#[derive(SchemaBridge)]
+#[schema(min_len = 8)]
+struct Code(#[schema(max_len = 3)] String);
+
+fn main() {}
@@ -0,0 +1,25 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+#[schema_bridge(ts_enum)]
+struct Status {
+    code: u8,
+}
+
+#[derive(SchemaBridge)]
+#[schema_bridge(ts_const_values)]
+struct Level(u8);
+
+#[derive(SchemaBridge)]
+#[schema_bridge(numeric)]
+struct Code(u8);
+
+#[derive(SchemaBridge)]
+#[schema_bridge(numeric)]
+#[repr(u8)]
+enum Event {
+    Start = 1,
+    Stop { code: u8 },
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+#[schema(min_len = 1)]
+struct User {
+    name: String,
+}
+
+#[derive(SchemaBridge)]
+#[schema(min = 0)]
+enum Level {
+    Low,
+    High,
+}
+
+#[derive(SchemaBridge)]
+#[schema(pattern = "^[a-z]+$")]
+struct Pair(String, String);
+
+// Constraints are checked against the proxy type
+#[derive(Clone, serde::Serialize, SchemaBridge)]
+#[serde(into = "String")]
+#[schema(min = 1)]
+struct Slug {
+    parts: Vec<String>,
+}
+
+impl From<Slug> for String {
+    fn from(slug: Slug) -> String {
+        slug.parts.join("-")
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+#[schema(rename = "Person")]
+struct User {
+    name: String,
+}
+
+#[derive(SchemaBridge)]
+struct UserId(#[schema(skip)] String);
+
+#[derive(SchemaBridge)]
+struct Account {
+    #[schema(ts_type = 42)]
+    balance: i64,
+}
+
+#[derive(SchemaBridge)]
+struct Ledger {
+    #[schema(items(ts_type = "Money"))]
+    entries: Vec<i64>,
+    #[schema(keys(rename = "id"))]
+    owners: std::collections::HashMap<String, u32>,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+struct Pair(#[schema(minlen = 1)] String, #[schema(min = 5, max = 1)] u32);
+
+#[derive(SchemaBridge)]
+struct Span(#[schema(min_len = 1)] u32, #[schema(rename = "end")] u32);
+
+#[derive(SchemaBridge)]
+struct Row(String, #[schema(required)] Option<u32>);
+
+#[derive(SchemaBridge)]
+enum Shape {
+    Circle(#[schema(min = 0)] f64),
+    Rect { #[schema(min = 0)] width: f64 },
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use schema_bridge::SchemaBridge;
+
+#[derive(SchemaBridge)]
+struct User {
+    #[schema(minlen = 3)]
+    name: String,
+}
+
+#[derive(SchemaBridge)]
+struct Post {
+    #[schema(items(maxlen = 32))]
+    tags: Vec<String>,
+}
+
+#[derive(SchemaBridge)]
+#[schema_bridge(brnad)]
+struct UserId(String);
+
+fn main() {}
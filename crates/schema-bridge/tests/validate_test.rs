@@ -0,0 +1,168 @@
+#![cfg(feature = "validate")]
+
+use schema_bridge::{CompiledSchema, Schema, SchemaBridge, ValidationError};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct User {
+    #[schema(min_len = 1, max_len = 50)]
+    name: String,
+    #[schema(min = 0, max = 150)]
+    age: u8,
+    #[schema(pattern = "^[^@]+@[^@]+$")]
+    email: Option<String>,
+    #[schema(items(min_len = 1))]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Active,
+    Suspended,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Batch {
+    users: Vec<User>,
+    status: Option<Status>,
+    counts: HashMap<u32, u32>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema_bridge(brand)]
+struct UserId(String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Ticket {
+    owner: UserId,
+    watchers: Vec<UserId>,
+    assignee: Option<UserId>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Category {
+    #[schema(min_len = 1)]
+    name: String,
+    children: Vec<Category>,
+}
+
+fn messages(schema: Schema, value: Value) -> Vec<String> {
+    CompiledSchema::compile(&schema)
+        .unwrap()
+        .validate(&value)
+        .err()
+        .unwrap_or_default()
+        .iter()
+        .map(ValidationError::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_payload() {
+        let payload = json!({
+            "users": [{ "name": "Ann", "age": 30, "tags": ["a"] }],
+            "status": "active",
+            "counts": { "1": 2 }
+        });
+        assert_eq!(messages(Batch::to_schema(), payload), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_field_constraints() {
+        let user = json!({
+            "name": "",
+            "age": 200,
+            "email": "nobody",
+            "tags": ["ok", ""]
+        });
+        assert_eq!(
+            messages(User::to_schema(), user),
+            [
+                "age: must be at most 150",
+                "email: must match pattern `^[^@]+@[^@]+$`",
+                "name: must have at least 1 characters",
+                "tags[1]: must have at least 1 characters",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optional_fields_and_null() {
+        let user = json!({ "name": "Ann", "age": 1, "email": null, "tags": [] });
+        assert_eq!(messages(User::to_schema(), user), Vec::<String>::new());
+
+        let missing = json!({ "name": "Ann", "tags": [] });
+        assert_eq!(messages(User::to_schema(), missing), ["age: is required"]);
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        assert_eq!(
+            messages(Status::to_schema(), json!("deleted")),
+            ["must be one of 'active', 'suspended'"]
+        );
+
+        let payload = json!({ "users": [], "status": "gone", "counts": {} });
+        assert_eq!(
+            messages(Batch::to_schema(), payload),
+            ["status: must be one of 'active', 'suspended'"]
+        );
+    }
+
+    #[test]
+    fn test_map_keys_and_paths() {
+        let payload = json!({ "users": [], "counts": { "one": 1 } });
+        assert_eq!(
+            messages(Batch::to_schema(), payload),
+            ["counts.one: invalid key: must match pattern `^[0-9]+$`"]
+        );
+    }
+
+    #[test]
+    fn test_compiled_schema_is_reusable() {
+        let compiled = CompiledSchema::compile(User::schema_ref()).unwrap();
+        for age in [0, 75, 150] {
+            let user = json!({ "name": "Ann", "age": age, "tags": [] });
+            assert!(compiled.validate(&user).is_ok());
+        }
+        let user = json!({ "name": "Ann", "age": -1, "tags": [] });
+        assert!(compiled.validate(&user).is_err());
+    }
+
+    #[test]
+    fn test_branded_names_in_mismatches() {
+        let ticket = json!({ "owner": 7, "watchers": ["ann", false], "assignee": [] });
+        assert_eq!(
+            messages(Ticket::to_schema(), ticket),
+            [
+                "assignee: expected UserId (string), found array",
+                "owner: expected UserId (string), found number",
+                "watchers[1]: expected UserId (string), found boolean",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_types() {
+        let tree = json!({
+            "name": "root",
+            "children": [
+                { "name": "a", "children": [{ "name": "", "children": [] }] },
+                { "name": "b", "children": 3 }
+            ]
+        });
+        let expected = [
+            "children[0].children[0].name: must have at least 1 characters",
+            "children[1].children: expected array, found number",
+        ];
+        assert_eq!(messages(Category::to_schema(), tree.clone()), expected);
+        assert_eq!(messages(Category::schema_ref().clone(), tree), expected);
+    }
+}
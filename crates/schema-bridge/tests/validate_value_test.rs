@@ -0,0 +1,132 @@
+#![cfg(feature = "validate")]
+
+use schema_bridge::{validate_value, CompiledSchema, SchemaBridge, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Name(#[schema(min_len = 1, max_len = 64)] String);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[schema(min = 0, max = 150)]
+struct Age(u8);
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "snake_case")]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+struct Member {
+    name: Name,
+    age: Age,
+    nickname: Option<Name>,
+    role: Role,
+    #[schema(max_len = 2, items(min = 1))]
+    team_ids: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize, SchemaBridge)]
+struct Config {
+    #[schema(min = 1, max = 65535)]
+    port: u32,
+    #[schema(keys(pattern = "^[a-z]+$"))]
+    members: BTreeMap<String, Member>,
+}
+
+#[derive(Serialize, SchemaBridge)]
+struct Borrowed<'a> {
+    #[schema(min_len = 1)]
+    name: &'a str,
+}
+
+fn member(age: u8) -> Member {
+    Member {
+        name: Name("Ann".to_string()),
+        age: Age(age),
+        nickname: None,
+        role: Role::Admin,
+        team_ids: vec![1],
+    }
+}
+
+fn messages(result: Result<(), Vec<ValidationError>>) -> Vec<String> {
+    result
+        .err()
+        .unwrap_or_default()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_value() {
+        assert_eq!(validate_value(&member(30)), Ok(()));
+    }
+
+    #[test]
+    fn test_field_and_newtype_constraints() {
+        let member = Member {
+            name: Name(String::new()),
+            nickname: Some(Name("x".repeat(65))),
+            team_ids: vec![0, 1, 2],
+            ..member(151)
+        };
+        assert_eq!(
+            messages(validate_value(&member)),
+            [
+                "name: must have at least 1 characters",
+                "age: must be at most 150",
+                "nickname: must have at most 64 characters",
+                "teamIds[0]: must be at least 1",
+                "teamIds: must have at most 2 items",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_maps() {
+        let config = Config {
+            port: 0,
+            members: BTreeMap::from([
+                ("ann".to_string(), member(30)),
+                ("Bob".to_string(), member(200)),
+            ]),
+        };
+        assert_eq!(
+            messages(validate_value(&config)),
+            [
+                "port: must be at least 1",
+                "members.Bob: invalid key: must match pattern `^[a-z]+$`",
+                "members.Bob.age: must be at most 150",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_json_validation() {
+        let config = Config {
+            port: 8080,
+            members: BTreeMap::from([("ann".to_string(), member(151))]),
+        };
+        let compiled = CompiledSchema::compile(Config::schema_ref()).unwrap();
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(compiled.validate(&json), validate_value(&config));
+    }
+
+    #[test]
+    fn test_borrowed_types() {
+        let compiled = CompiledSchema::compile(&Borrowed::to_schema()).unwrap();
+        assert_eq!(
+            messages(compiled.validate_value(&Borrowed { name: "" })),
+            ["name: must have at least 1 characters"]
+        );
+    }
+}
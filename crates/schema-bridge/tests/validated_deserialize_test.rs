@@ -0,0 +1,114 @@
+#![cfg(feature = "validate")]
+
+use schema_bridge::{
+    from_str_validated, from_value_validated, DeserializeError, SchemaBridge, ValidationError,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Serialize, Deserialize, SchemaBridge)]
+struct User {
+    #[schema(min_len = 1)]
+    name: String,
+    #[schema(min = 0, max = 150)]
+    age: i32,
+    email: Option<String>,
+    #[serde(default)]
+    #[schema(max_len = 3)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, SchemaBridge)]
+struct Team {
+    lead: User,
+    size: u8,
+}
+
+fn error(path: &str, message: &str) -> ValidationError {
+    ValidationError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_input() {
+        let user: User = from_str_validated(r#"{ "name": "Ann", "age": 30 }"#).unwrap();
+        assert_eq!(user.age, 30);
+        assert!(user.tags.is_empty());
+    }
+
+    #[test]
+    fn test_constraints_are_enforced() {
+        let err = from_str_validated::<User>(r#"{ "name": "", "age": 200 }"#).unwrap_err();
+        assert_eq!(
+            err.errors(),
+            [
+                error("age", "must be at most 150"),
+                error("name", "must have at least 1 characters"),
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "age: must be at most 150; name: must have at least 1 characters"
+        );
+    }
+
+    #[test]
+    fn test_nested_paths() {
+        let value = json!({ "lead": { "name": "Ann", "age": -1, "tags": ["a", "b", "c", "d"] }, "size": 3 });
+        let err = from_value_validated::<Team>(value).unwrap_err();
+        assert_eq!(
+            err.errors(),
+            [
+                error("lead.age", "must be at least 0"),
+                error("lead.tags", "must have at most 3 items"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serde_failures_are_keyed_by_path() {
+        let value = json!({ "lead": { "name": "Ann", "age": 30 }, "size": 300 });
+        let err = from_value_validated::<Team>(value).unwrap_err();
+        assert_eq!(
+            err.errors(),
+            [error("size", "invalid value: integer `300`, expected u8")]
+        );
+    }
+
+    #[test]
+    fn test_constraint_and_serde_failures_are_merged() {
+        let value = json!({ "lead": { "name": "", "age": 30 }, "size": 300 });
+        let err = from_value_validated::<Team>(value).unwrap_err();
+        assert_eq!(
+            err.errors(),
+            [
+                error("lead.name", "must have at least 1 characters"),
+                error("size", "invalid value: integer `300`, expected u8"),
+            ]
+        );
+
+        // A type mismatch the schema reports is not repeated by serde
+        let value = json!({ "lead": { "name": "", "age": "old" }, "size": 3 });
+        let err = from_value_validated::<Team>(value).unwrap_err();
+        assert_eq!(
+            err.errors(),
+            [
+                error("lead.age", "expected integer, found string"),
+                error("lead.name", "must have at least 1 characters"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let err = from_str_validated::<User>("{ \"name\": ").unwrap_err();
+        assert!(matches!(err, DeserializeError::Syntax(_)));
+        assert!(err.errors().is_empty());
+    }
+}